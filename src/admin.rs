@@ -0,0 +1,468 @@
+// Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Admin HTTP API for inspecting sync state and forcing resyncs.
+//! Every request must carry the configured bearer token.
+
+use crate::sync::secrets::{enabled_secrets, fetch_secret, is_enabled, secret_key};
+use crate::sync::{SecretStores, SyncEvent, SyncManagerHandle, SyncState};
+use crate::types::cluster::Cluster;
+use axum::{
+    Json, Router,
+    extract::{Path, Request, State},
+    http::{
+        StatusCode,
+        header::{AUTHORIZATION, WWW_AUTHENTICATE},
+    },
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+use k8s_openapi::chrono::{DateTime, Utc};
+use kube::runtime::reflector::{ObjectRef, Store};
+use kube::{Client, ResourceExt};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::watch;
+use tracing::{error, info};
+
+/// Everything the admin API reads from or sends to
+#[derive(Clone)]
+pub struct AdminState {
+    token: Arc<str>,
+    handle: SyncManagerHandle,
+    client: Client,
+    secrets: SecretStores,
+    clusters: Store<Cluster>,
+    /// Whether this replica leads; only the leader acts on resync requests
+    leader: watch::Receiver<bool>,
+}
+
+impl AdminState {
+    pub fn new(
+        token: &str,
+        handle: SyncManagerHandle,
+        client: Client,
+        secrets: SecretStores,
+        clusters: Store<Cluster>,
+        leader: watch::Receiver<bool>,
+    ) -> Self {
+        Self {
+            token: token.into(),
+            handle,
+            client,
+            secrets,
+            clusters,
+            leader,
+        }
+    }
+}
+
+/// A Rancher cluster, whether it received its initial sync and the
+/// permissions its access check found missing. The manager cluster is
+/// listed for its permissions only, as secrets are never synced to it.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct ClusterView {
+    name: String,
+    ready: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    synced: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    missing_permissions: Vec<String>,
+}
+
+/// An enabled secret with its last sync result on each cluster
+#[derive(Debug, Serialize)]
+struct SecretView {
+    secret: String,
+    clusters: BTreeMap<String, SyncStateView>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "state", rename_all = "lowercase")]
+enum SyncStateView {
+    Synced {
+        at: Time,
+        hash: String,
+    },
+    #[serde(rename = "dryRun")]
+    DryRun {
+        at: Time,
+    },
+    #[serde(rename_all = "camelCase")]
+    Retrying {
+        attempts: u32,
+        error: String,
+        next_attempt: Time,
+    },
+    Failed {
+        attempts: u32,
+        error: String,
+    },
+}
+
+impl From<SyncState> for SyncStateView {
+    fn from(state: SyncState) -> Self {
+        match state {
+            SyncState::Synced { at, hash } => SyncStateView::Synced { at: time(at), hash },
+            SyncState::DryRun { at } => SyncStateView::DryRun { at: time(at) },
+            SyncState::Retrying {
+                attempts,
+                error,
+                next_attempt,
+            } => SyncStateView::Retrying {
+                attempts,
+                error,
+                next_attempt: time(next_attempt),
+            },
+            SyncState::Failed { attempts, error } => SyncStateView::Failed { attempts, error },
+        }
+    }
+}
+
+fn time(at: SystemTime) -> Time {
+    Time(DateTime::<Utc>::from(at))
+}
+
+/// Routes under `/admin`, all requiring the bearer token
+pub fn router(state: AdminState) -> Router {
+    Router::new()
+        .route("/admin/clusters", get(list_clusters))
+        .route("/admin/clusters/{name}/resync", post(resync_cluster))
+        .route("/admin/secrets", get(list_secrets))
+        .route(
+            "/admin/secrets/{namespace}/{name}/resync",
+            post(resync_secret),
+        )
+        .route_layer(middleware::from_fn_with_state(state.clone(), authenticate))
+        .with_state(state)
+}
+
+async fn authenticate(State(state): State<AdminState>, request: Request, next: Next) -> Response {
+    let token = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match token {
+        Some(token) if token_matches(&state.token, token) => next.run(request).await,
+        _ => (
+            StatusCode::UNAUTHORIZED,
+            [(WWW_AUTHENTICATE, "Bearer")],
+            "invalid or missing bearer token",
+        )
+            .into_response(),
+    }
+}
+
+/// Compare digests rather than the tokens themselves, so the time taken does
+/// not reveal how much of the token matched
+fn token_matches(expected: &str, given: &str) -> bool {
+    Sha256::digest(expected) == Sha256::digest(given)
+}
+
+async fn list_clusters(State(state): State<AdminState>) -> Json<Vec<ClusterView>> {
+    let synced = state.handle.synced_clusters().await;
+    let mut clusters: Vec<ClusterView> = state
+        .clusters
+        .state()
+        .iter()
+        .map(|cluster| ClusterView {
+            name: cluster.name_any(),
+            ready: cluster.is_ready(),
+            synced: (!cluster.is_local()).then(|| synced.contains(&cluster.name_any())),
+            missing_permissions: state
+                .handle
+                .status()
+                .missing_permissions(&cluster.name_any()),
+        })
+        .collect();
+    clusters.sort_by(|a, b| a.name.cmp(&b.name));
+    Json(clusters)
+}
+
+async fn list_secrets(State(state): State<AdminState>) -> Json<Vec<SecretView>> {
+    let mut secrets: Vec<SecretView> = enabled_secrets(&state.secrets)
+        .iter()
+        .map(|meta| {
+            let secret = format!(
+                "{}/{}",
+                meta.namespace().unwrap_or_default(),
+                meta.name_any()
+            );
+            let clusters = state
+                .handle
+                .status()
+                .secret_states(&secret)
+                .into_iter()
+                .map(|(cluster, sync_state)| (cluster, sync_state.into()))
+                .collect();
+            SecretView { secret, clusters }
+        })
+        .collect();
+    secrets.sort_by(|a, b| a.secret.cmp(&b.secret));
+    Json(secrets)
+}
+
+async fn resync_cluster(
+    State(state): State<AdminState>,
+    Path(name): Path<String>,
+) -> (StatusCode, String) {
+    if !*state.leader.borrow() {
+        return not_leader();
+    }
+    // Clusters live in the Rancher workspace namespaces, but are identified by name
+    let cluster = match state.clusters.find(|cluster| cluster.name_any() == name) {
+        Some(cluster) if !cluster.is_local() => cluster,
+        _ => return (StatusCode::NOT_FOUND, format!("cluster {} not found", name)),
+    };
+    if !cluster.is_ready() {
+        return (
+            StatusCode::CONFLICT,
+            format!("cluster {} is not ready", name),
+        );
+    }
+
+    info!(
+        "Resync of cluster '{}' requested through the admin API",
+        name
+    );
+    let cluster = (*cluster).clone();
+    state
+        .handle
+        .send(SyncEvent::ResyncCluster { cluster })
+        .await;
+    (
+        StatusCode::ACCEPTED,
+        format!("resync of cluster {} queued", name),
+    )
+}
+
+async fn resync_secret(
+    State(state): State<AdminState>,
+    Path((namespace, name)): Path<(String, String)>,
+) -> (StatusCode, String) {
+    if !*state.leader.borrow() {
+        return not_leader();
+    }
+    let key = format!("{}/{}", namespace, name);
+    let meta = match state.secrets.get(&ObjectRef::new(&name).within(&namespace)) {
+        Some(meta) if is_enabled(&meta.metadata) => meta,
+        _ => {
+            return (
+                StatusCode::NOT_FOUND,
+                format!("enabled secret {} not found", key),
+            );
+        }
+    };
+
+    let secret = match fetch_secret(&state.client, &meta).await {
+        Ok(Some(secret)) => secret,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                format!("enabled secret {} not found", key),
+            );
+        }
+        Err(e) => {
+            error!("Failed to fetch secret {}: {}", key, e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+        }
+    };
+
+    info!(
+        "Resync of secret {} requested through the admin API",
+        secret_key(&secret)
+    );
+    state.handle.send(SyncEvent::ResyncSecret { secret }).await;
+    (
+        StatusCode::ACCEPTED,
+        format!("resync of secret {} queued", key),
+    )
+}
+
+fn not_leader() -> (StatusCode, String) {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        "not the leader, only the leading replica syncs secrets".to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::sync::SyncManager;
+    use crate::test_utils::{MockService, make_cluster, test_config};
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use kube::runtime::{reflector, watcher};
+    use tower::ServiceExt;
+
+    fn make_state(clusters: Vec<Cluster>, leader: bool) -> (AdminState, SyncManager) {
+        let config = Config {
+            admin_token: Some("s3cr3t".to_string()),
+            ..test_config()
+        };
+        let client = MockService::new().into_client();
+        let (secrets, _) = SecretStores::new(&config);
+        let (store, mut writer) = reflector::store();
+        for cluster in clusters {
+            writer.apply_watcher_event(&watcher::Event::Apply(cluster));
+        }
+        let (_, config) = watch::channel(config);
+        let (manager, handle) =
+            SyncManager::new(client.clone(), config, secrets.clone(), store.clone());
+        let (_, leader) = watch::channel(leader);
+        let state = AdminState::new("s3cr3t", handle, client, secrets, store, leader);
+        (state, manager)
+    }
+
+    async fn call(
+        state: &AdminState,
+        method: &str,
+        path: &str,
+        token: Option<&str>,
+    ) -> (StatusCode, String) {
+        let mut request = Request::builder().method(method).uri(path);
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION, format!("Bearer {}", token));
+        }
+        let response = router(state.clone())
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8_lossy(&body).to_string())
+    }
+
+    #[tokio::test]
+    async fn test_requires_token() {
+        let (state, _manager) = make_state(Vec::new(), true);
+
+        assert_eq!(
+            call(&state, "GET", "/admin/clusters", None).await.0,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            call(&state, "GET", "/admin/clusters", Some("wrong"))
+                .await
+                .0,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            call(&state, "GET", "/admin/clusters", Some("s3cr3t"))
+                .await
+                .0,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_clusters() {
+        let (state, _manager) = make_state(
+            vec![
+                make_cluster("b", false),
+                make_cluster("a", true),
+                make_cluster("local", true),
+            ],
+            true,
+        );
+        state
+            .handle
+            .status()
+            .set_missing_permissions("a", vec!["create namespaces cluster-wide".to_string()]);
+        state
+            .handle
+            .status()
+            .set_missing_permissions("local", vec!["list secrets cluster-wide".to_string()]);
+
+        let (status, body) = call(&state, "GET", "/admin/clusters", Some("s3cr3t")).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            r#"[{"name":"a","ready":true,"synced":false,"missingPermissions":["create namespaces cluster-wide"]},{"name":"b","ready":false,"synced":false},{"name":"local","ready":true,"missingPermissions":["list secrets cluster-wide"]}]"#
+        );
+    }
+
+    #[test]
+    fn test_sync_state_view() {
+        let view = SyncStateView::from(SyncState::Retrying {
+            attempts: 2,
+            error: "timeout".to_string(),
+            next_attempt: SystemTime::UNIX_EPOCH,
+        });
+
+        assert_eq!(
+            serde_json::to_string(&view).unwrap(),
+            r#"{"state":"retrying","attempts":2,"error":"timeout","nextAttempt":"1970-01-01T00:00:00Z"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resync_cluster() {
+        let (state, _manager) = make_state(
+            vec![make_cluster("ready", true), make_cluster("down", false)],
+            true,
+        );
+        let token = Some("s3cr3t");
+
+        assert_eq!(
+            call(&state, "POST", "/admin/clusters/ready/resync", token)
+                .await
+                .0,
+            StatusCode::ACCEPTED
+        );
+        assert_eq!(
+            call(&state, "POST", "/admin/clusters/down/resync", token)
+                .await
+                .0,
+            StatusCode::CONFLICT
+        );
+        assert_eq!(
+            call(&state, "POST", "/admin/clusters/unknown/resync", token)
+                .await
+                .0,
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resync_refused_on_standby() {
+        let (state, _manager) = make_state(vec![make_cluster("ready", true)], false);
+
+        let (status, _) = call(
+            &state,
+            "POST",
+            "/admin/clusters/ready/resync",
+            Some("s3cr3t"),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_resync_unknown_secret() {
+        let (state, _manager) = make_state(Vec::new(), true);
+
+        let (status, _) = call(
+            &state,
+            "POST",
+            "/admin/secrets/default/creds/resync",
+            Some("s3cr3t"),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
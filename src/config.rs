@@ -1,29 +1,386 @@
 // Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
 // SPDX-License-Identifier: Apache-2.0
-use anyhow::{Context, Result};
+use crate::constants::http::DEFAULT_PORT;
+use crate::constants::leader::DEFAULT_LEASE_NAME;
+use crate::constants::state::{DEFAULT_CONFIG_MAP_NAME, DEFAULT_SHARDS};
+use crate::constants::sync::{
+    DEFAULT_DEBOUNCE_MS, DEFAULT_MAX_CONCURRENT_SYNCS, DEFAULT_SHUTDOWN_TIMEOUT_SECS,
+    DEFAULT_SYNC_TIMEOUT_SECS,
+};
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
 use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
 
-/// Operator configuration loaded from environment variables
-#[derive(Debug, Clone)]
+/// Operator configuration loaded from an optional config file and environment variables
+#[derive(Debug, Clone, PartialEq)]
 pub struct Config {
     /// Default namespace to copy secrets to in downstream clusters
     pub default_target_namespace: String,
     pub testing_mode: bool,
+    /// Maximum number of clusters that are synced to concurrently
+    pub max_concurrent_syncs: usize,
+    /// Maximum time a single secret copy to a cluster may take
+    pub sync_timeout: Duration,
+    /// Window during which repeated events for the same secret or cluster are coalesced
+    pub debounce: Duration,
+    /// Only watch secrets carrying the enabled label, filtered server-side.
+    /// When false, all secrets are watched and either the label or the
+    /// annotation enables syncing.
+    pub secret_label_selector: bool,
+    /// Namespaces to read source secrets from. Empty means all namespaces.
+    pub watch_namespaces: Vec<String>,
+    /// Port of the HTTP server serving `/metrics` and the health probes
+    pub http_port: u16,
+    /// Lease to compete for when running multiple replicas. None runs without leader election.
+    pub leader_election: Option<LeaderElectionConfig>,
+    /// Time to finish in-flight syncs after receiving SIGTERM
+    pub shutdown_timeout: Duration,
+    pub log_format: LogFormat,
+    /// Log filter directives, e.g. `info` or `outrider=debug,kube=warn`.
+    /// None falls back to `RUST_LOG`.
+    pub log_level: Option<String>,
+    /// OTLP gRPC endpoint to export trace spans to. None disables trace export.
+    pub otlp_endpoint: Option<String>,
+    /// Only validate downstream writes with server-side dry runs, logging the
+    /// changes they would make
+    pub dry_run: bool,
+    /// Bearer token required by the admin API. None disables the admin API.
+    pub admin_token: Option<String>,
+    /// Queue secrets without writing them to any cluster
+    pub paused: bool,
+    /// ConfigMaps to persist the sync state in across restarts. None keeps it in memory only.
+    pub state_config_map: Option<StateConfigMapConfig>,
+}
+
+/// Format of the log output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per line, including the fields of the enclosing spans
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Lease used to elect the replica that syncs secrets
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeaderElectionConfig {
+    pub lease_name: String,
+    /// Namespace of the Lease, normally the operator's own namespace
+    pub namespace: String,
+    /// Identity of this replica, normally the pod name
+    pub identity: String,
+}
+
+/// ConfigMaps holding the content hashes of the secrets synced to each cluster.
+/// Each cluster's state is kept in one of `shards` ConfigMaps, keeping every
+/// ConfigMap well below the object size limit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateConfigMapConfig {
+    /// Name prefix of the ConfigMaps
+    pub name: String,
+    /// Namespace of the ConfigMaps, normally the operator's own namespace
+    pub namespace: String,
+    pub shards: usize,
+}
+
+impl StateConfigMapConfig {
+    /// Names of the ConfigMaps, `<name>-0` up to `<name>-<shards - 1>`
+    pub fn config_map_names(&self) -> Vec<String> {
+        (0..self.shards)
+            .map(|shard| format!("{}-{}", self.name, shard))
+            .collect()
+    }
+}
+
+/// Settings read from the config file. Keys are the lower-case names of the
+/// corresponding environment variables, which take precedence.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileConfig {
+    default_target_namespace: Option<String>,
+    max_concurrent_syncs: Option<usize>,
+    sync_timeout_secs: Option<u64>,
+    sync_debounce_ms: Option<u64>,
+    secret_label_selector: Option<bool>,
+    watch_namespaces: Option<Vec<String>>,
+    http_port: Option<u16>,
+    leader_election: Option<bool>,
+    lease_name: Option<String>,
+    shutdown_timeout_secs: Option<u64>,
+    log_format: Option<LogFormat>,
+    log_level: Option<String>,
+    otel_exporter_otlp_endpoint: Option<String>,
+    dry_run: Option<bool>,
+    admin_token: Option<String>,
+    paused: Option<bool>,
+    persist_state: Option<bool>,
+    state_config_map: Option<String>,
+    state_config_map_shards: Option<usize>,
 }
 
 impl Config {
-    /// Load configuration from environment variables
+    /// Load configuration from the file named by `CONFIG_FILE`, if set,
+    /// with environment variables taking precedence
+    pub fn load() -> Result<Self> {
+        match config_file() {
+            Some(path) => Self::from_sources(read_config_file(&path)?),
+            None => Self::from_env(),
+        }
+    }
+
+    /// Load configuration from environment variables only
     pub fn from_env() -> Result<Self> {
+        Self::from_sources(FileConfig::default())
+    }
+
+    fn from_sources(file: FileConfig) -> Result<Self> {
         let default_target_namespace = env::var("DEFAULT_TARGET_NAMESPACE")
+            .ok()
+            .or(file.default_target_namespace)
             .context("DEFAULT_TARGET_NAMESPACE environment variable not set")?;
-         // For testing, uses the KUBECONFIG env var to create downstream clients instead of fetching kubeconfig from secrets
-        let testing_mode: bool = env::var("TESTING_MODE").unwrap_or("false".to_string()).parse().unwrap_or(false);
+        // For testing, uses the KUBECONFIG env var to create downstream clients instead of fetching kubeconfig from secrets
+        let testing_mode: bool = env::var("TESTING_MODE")
+            .unwrap_or("false".to_string())
+            .parse()
+            .unwrap_or(false);
+        let max_concurrent_syncs = setting(
+            "MAX_CONCURRENT_SYNCS",
+            file.max_concurrent_syncs,
+            DEFAULT_MAX_CONCURRENT_SYNCS,
+            |n| *n > 0,
+        )?;
+        let sync_timeout_secs = setting(
+            "SYNC_TIMEOUT_SECS",
+            file.sync_timeout_secs,
+            DEFAULT_SYNC_TIMEOUT_SECS,
+            |n| *n > 0,
+        )?;
+        let debounce_ms = setting(
+            "SYNC_DEBOUNCE_MS",
+            file.sync_debounce_ms,
+            DEFAULT_DEBOUNCE_MS,
+            |_| true,
+        )?;
+        let secret_label_selector = setting(
+            "SECRET_LABEL_SELECTOR",
+            file.secret_label_selector,
+            false,
+            |_| true,
+        )?;
+        let watch_namespaces = match env::var("WATCH_NAMESPACES") {
+            Ok(v) => parse_namespaces(&v),
+            Err(_) => parse_namespaces(&file.watch_namespaces.unwrap_or_default().join(",")),
+        };
+        let http_port = setting("HTTP_PORT", file.http_port, DEFAULT_PORT, |p| *p > 0)?;
+        let shutdown_timeout_secs = setting(
+            "SHUTDOWN_TIMEOUT_SECS",
+            file.shutdown_timeout_secs,
+            DEFAULT_SHUTDOWN_TIMEOUT_SECS,
+            |_| true,
+        )?;
+        let log_format = setting("LOG_FORMAT", file.log_format, LogFormat::default(), |_| {
+            true
+        })?;
+        let log_level = env::var("LOG_LEVEL")
+            .ok()
+            .or(file.log_level)
+            .filter(|v| !v.trim().is_empty());
+        let otlp_endpoint = env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+            .ok()
+            .or(file.otel_exporter_otlp_endpoint)
+            .filter(|v| !v.trim().is_empty());
+        let dry_run = setting("DRY_RUN", file.dry_run, false, |_| true)?;
+        let paused = setting("PAUSED", file.paused, false, |_| true)?;
+        let admin_token = env::var("ADMIN_TOKEN")
+            .ok()
+            .or(file.admin_token)
+            .filter(|v| !v.trim().is_empty());
+        let leader_election = setting("LEADER_ELECTION", file.leader_election, false, |_| true)?;
+        let leader_election = if leader_election {
+            Some(LeaderElectionConfig {
+                lease_name: env::var("LEASE_NAME")
+                    .ok()
+                    .or(file.lease_name)
+                    .unwrap_or(DEFAULT_LEASE_NAME.to_string()),
+                namespace: env::var("POD_NAMESPACE")
+                    .context("POD_NAMESPACE environment variable required for leader election")?,
+                identity: env::var("POD_NAME")
+                    .context("POD_NAME environment variable required for leader election")?,
+            })
+        } else {
+            None
+        };
+        let persist_state = setting("PERSIST_STATE", file.persist_state, false, |_| true)?;
+        let state_config_map = if persist_state {
+            Some(StateConfigMapConfig {
+                name: env::var("STATE_CONFIG_MAP")
+                    .ok()
+                    .or(file.state_config_map)
+                    .unwrap_or(DEFAULT_CONFIG_MAP_NAME.to_string()),
+                namespace: env::var("POD_NAMESPACE")
+                    .context("POD_NAMESPACE environment variable required to persist state")?,
+                shards: setting(
+                    "STATE_CONFIG_MAP_SHARDS",
+                    file.state_config_map_shards,
+                    DEFAULT_SHARDS,
+                    |n| *n > 0,
+                )?,
+            })
+        } else {
+            None
+        };
 
         Ok(Config {
             default_target_namespace,
             testing_mode,
+            max_concurrent_syncs,
+            sync_timeout: Duration::from_secs(sync_timeout_secs),
+            debounce: Duration::from_millis(debounce_ms),
+            secret_label_selector,
+            watch_namespaces,
+            http_port,
+            leader_election,
+            shutdown_timeout: Duration::from_secs(shutdown_timeout_secs),
+            log_format,
+            log_level,
+            otlp_endpoint,
+            dry_run,
+            admin_token,
+            paused,
+            state_config_map,
         })
     }
+
+    /// Take over the settings of a reloaded configuration that apply without a
+    /// restart. Returns the new configuration and the names of changed settings
+    /// that only take effect after a restart, which keep their current value.
+    pub fn reload(&self, new: Config) -> (Config, Vec<&'static str>) {
+        let restart_only = [
+            ("TESTING_MODE", self.testing_mode != new.testing_mode),
+            (
+                "SECRET_LABEL_SELECTOR",
+                self.secret_label_selector != new.secret_label_selector,
+            ),
+            (
+                "WATCH_NAMESPACES",
+                self.watch_namespaces != new.watch_namespaces,
+            ),
+            ("HTTP_PORT", self.http_port != new.http_port),
+            (
+                "LEADER_ELECTION",
+                self.leader_election != new.leader_election,
+            ),
+            ("LOG_FORMAT", self.log_format != new.log_format),
+            ("LOG_LEVEL", self.log_level != new.log_level),
+            (
+                "OTEL_EXPORTER_OTLP_ENDPOINT",
+                self.otlp_endpoint != new.otlp_endpoint,
+            ),
+            ("DRY_RUN", self.dry_run != new.dry_run),
+            ("ADMIN_TOKEN", self.admin_token != new.admin_token),
+            (
+                "PERSIST_STATE",
+                self.state_config_map != new.state_config_map,
+            ),
+        ];
+        let ignored = restart_only
+            .into_iter()
+            .filter(|(_, changed)| *changed)
+            .map(|(name, _)| name)
+            .collect();
+
+        let config = Config {
+            default_target_namespace: new.default_target_namespace,
+            max_concurrent_syncs: new.max_concurrent_syncs,
+            sync_timeout: new.sync_timeout,
+            debounce: new.debounce,
+            shutdown_timeout: new.shutdown_timeout,
+            paused: new.paused,
+            ..self.clone()
+        };
+        (config, ignored)
+    }
+}
+
+/// Path of the config file, from the `CONFIG_FILE` environment variable
+pub fn config_file() -> Option<PathBuf> {
+    env::var_os("CONFIG_FILE")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+fn read_config_file(path: &Path) -> Result<FileConfig> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file {}", path.display()))?;
+    parse_config_file(&contents).with_context(|| format!("Invalid config file {}", path.display()))
+}
+
+fn parse_config_file(contents: &str) -> Result<FileConfig> {
+    // An empty file sets nothing
+    if contents.trim().is_empty() {
+        return Ok(FileConfig::default());
+    }
+    Ok(serde_yaml::from_str(contents)?)
+}
+
+/// Read a setting from its environment variable, falling back to the config
+/// file and then to `default`
+fn setting<T: FromStr>(
+    name: &str,
+    file_value: Option<T>,
+    default: T,
+    valid: impl Fn(&T) -> bool,
+) -> Result<T> {
+    if env::var(name).is_ok() {
+        return parse_env(name, default, valid);
+    }
+    match file_value {
+        Some(value) if !valid(&value) => {
+            bail!("Invalid value for {} in config file", name.to_lowercase())
+        }
+        Some(value) => Ok(value),
+        None => Ok(default),
+    }
+}
+
+/// Parse an optional environment variable, falling back to `default` when it is not set
+fn parse_env<T: FromStr>(name: &str, default: T, valid: impl Fn(&T) -> bool) -> Result<T> {
+    match env::var(name) {
+        Ok(v) => v
+            .parse::<T>()
+            .ok()
+            .filter(|parsed| valid(parsed))
+            .with_context(|| format!("Invalid value '{}' for {}", v, name)),
+        Err(_) => Ok(default),
+    }
+}
+
+/// Parse a comma-separated list of namespaces, ignoring whitespace and empty entries
+fn parse_namespaces(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|ns| !ns.is_empty())
+        .map(String::from)
+        .collect()
 }
 
 #[cfg(test)]
@@ -72,11 +429,48 @@ mod tests {
             &[
                 ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
                 ("TESTING_MODE", None),
+                ("MAX_CONCURRENT_SYNCS", None),
+                ("SYNC_TIMEOUT_SECS", None),
+                ("SYNC_DEBOUNCE_MS", None),
+                ("SECRET_LABEL_SELECTOR", None),
+                ("WATCH_NAMESPACES", None),
+                ("HTTP_PORT", None),
+                ("LEADER_ELECTION", None),
+                ("SHUTDOWN_TIMEOUT_SECS", None),
+                ("LOG_FORMAT", None),
+                ("LOG_LEVEL", None),
+                ("OTEL_EXPORTER_OTLP_ENDPOINT", None),
+                ("DRY_RUN", None),
+                ("ADMIN_TOKEN", None),
+                ("PAUSED", None),
+                ("PERSIST_STATE", None),
+                ("STATE_CONFIG_MAP_SHARDS", None),
             ],
             || {
                 let config = Config::from_env().unwrap();
                 assert_eq!(config.default_target_namespace, "my-namespace");
                 assert!(!config.testing_mode);
+                assert_eq!(config.max_concurrent_syncs, DEFAULT_MAX_CONCURRENT_SYNCS);
+                assert_eq!(
+                    config.sync_timeout,
+                    Duration::from_secs(DEFAULT_SYNC_TIMEOUT_SECS)
+                );
+                assert_eq!(config.debounce, Duration::from_millis(DEFAULT_DEBOUNCE_MS));
+                assert!(!config.secret_label_selector);
+                assert!(config.watch_namespaces.is_empty());
+                assert_eq!(config.http_port, DEFAULT_PORT);
+                assert!(config.leader_election.is_none());
+                assert_eq!(
+                    config.shutdown_timeout,
+                    Duration::from_secs(DEFAULT_SHUTDOWN_TIMEOUT_SECS)
+                );
+                assert_eq!(config.log_format, LogFormat::Text);
+                assert!(config.log_level.is_none());
+                assert!(config.otlp_endpoint.is_none());
+                assert!(!config.dry_run);
+                assert!(config.admin_token.is_none());
+                assert!(!config.paused);
+                assert!(config.state_config_map.is_none());
             },
         );
     }
@@ -99,17 +493,16 @@ mod tests {
     #[test]
     fn test_from_env_missing_namespace() {
         with_env_vars(
-            &[
-                ("DEFAULT_TARGET_NAMESPACE", None),
-                ("TESTING_MODE", None),
-            ],
+            &[("DEFAULT_TARGET_NAMESPACE", None), ("TESTING_MODE", None)],
             || {
                 let result = Config::from_env();
                 assert!(result.is_err());
-                assert!(result
-                    .unwrap_err()
-                    .to_string()
-                    .contains("DEFAULT_TARGET_NAMESPACE"));
+                assert!(
+                    result
+                        .unwrap_err()
+                        .to_string()
+                        .contains("DEFAULT_TARGET_NAMESPACE")
+                );
             },
         );
     }
@@ -128,4 +521,405 @@ mod tests {
             },
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_from_env_max_concurrent_syncs() {
+        with_env_vars(
+            &[
+                ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
+                ("MAX_CONCURRENT_SYNCS", Some("32")),
+            ],
+            || {
+                let config = Config::from_env().unwrap();
+                assert_eq!(config.max_concurrent_syncs, 32);
+            },
+        );
+    }
+
+    #[test]
+    fn test_from_env_invalid_max_concurrent_syncs() {
+        for value in ["0", "lots"] {
+            with_env_vars(
+                &[
+                    ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
+                    ("MAX_CONCURRENT_SYNCS", Some(value)),
+                ],
+                || {
+                    let result = Config::from_env();
+                    assert!(result.is_err());
+                    assert!(
+                        result
+                            .unwrap_err()
+                            .to_string()
+                            .contains("MAX_CONCURRENT_SYNCS")
+                    );
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_env_sync_timeout() {
+        with_env_vars(
+            &[
+                ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
+                ("SYNC_TIMEOUT_SECS", Some("5")),
+            ],
+            || {
+                let config = Config::from_env().unwrap();
+                assert_eq!(config.sync_timeout, Duration::from_secs(5));
+            },
+        );
+    }
+
+    #[test]
+    fn test_from_env_debounce() {
+        with_env_vars(
+            &[
+                ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
+                ("SYNC_DEBOUNCE_MS", Some("0")),
+            ],
+            || {
+                let config = Config::from_env().unwrap();
+                assert_eq!(config.debounce, Duration::ZERO);
+            },
+        );
+    }
+
+    #[test]
+    fn test_from_env_secret_label_selector() {
+        with_env_vars(
+            &[
+                ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
+                ("SECRET_LABEL_SELECTOR", Some("true")),
+            ],
+            || {
+                let config = Config::from_env().unwrap();
+                assert!(config.secret_label_selector);
+            },
+        );
+    }
+
+    #[test]
+    fn test_from_env_watch_namespaces() {
+        with_env_vars(
+            &[
+                ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
+                ("WATCH_NAMESPACES", Some(" team-a, team-b,,")),
+            ],
+            || {
+                let config = Config::from_env().unwrap();
+                assert_eq!(config.watch_namespaces, vec!["team-a", "team-b"]);
+            },
+        );
+    }
+
+    #[test]
+    fn test_from_env_invalid_http_port() {
+        for value in ["0", "70000"] {
+            with_env_vars(
+                &[
+                    ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
+                    ("HTTP_PORT", Some(value)),
+                ],
+                || {
+                    let result = Config::from_env();
+                    assert!(result.is_err());
+                    assert!(result.unwrap_err().to_string().contains("HTTP_PORT"));
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_env_leader_election() {
+        with_env_vars(
+            &[
+                ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
+                ("LEADER_ELECTION", Some("true")),
+                ("LEASE_NAME", None),
+                ("POD_NAMESPACE", Some("outrider-system")),
+                ("POD_NAME", Some("outrider-abc")),
+            ],
+            || {
+                let config = Config::from_env().unwrap();
+                assert_eq!(
+                    config.leader_election,
+                    Some(LeaderElectionConfig {
+                        lease_name: DEFAULT_LEASE_NAME.to_string(),
+                        namespace: "outrider-system".to_string(),
+                        identity: "outrider-abc".to_string(),
+                    })
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_from_env_leader_election_requires_pod_name() {
+        with_env_vars(
+            &[
+                ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
+                ("LEADER_ELECTION", Some("true")),
+                ("POD_NAMESPACE", Some("outrider-system")),
+                ("POD_NAME", None),
+            ],
+            || {
+                let result = Config::from_env();
+                assert!(result.unwrap_err().to_string().contains("POD_NAME"));
+            },
+        );
+    }
+
+    #[test]
+    fn test_from_env_shutdown_timeout() {
+        with_env_vars(
+            &[
+                ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
+                ("SHUTDOWN_TIMEOUT_SECS", Some("55")),
+            ],
+            || {
+                let config = Config::from_env().unwrap();
+                assert_eq!(config.shutdown_timeout, Duration::from_secs(55));
+            },
+        );
+    }
+
+    #[test]
+    fn test_from_env_log_settings() {
+        with_env_vars(
+            &[
+                ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
+                ("LOG_FORMAT", Some("JSON")),
+                ("LOG_LEVEL", Some("outrider=debug")),
+            ],
+            || {
+                let config = Config::from_env().unwrap();
+                assert_eq!(config.log_format, LogFormat::Json);
+                assert_eq!(config.log_level.as_deref(), Some("outrider=debug"));
+            },
+        );
+    }
+
+    #[test]
+    fn test_from_env_otlp_endpoint() {
+        with_env_vars(
+            &[
+                ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
+                (
+                    "OTEL_EXPORTER_OTLP_ENDPOINT",
+                    Some("http://otel-collector:4317"),
+                ),
+            ],
+            || {
+                let config = Config::from_env().unwrap();
+                assert_eq!(
+                    config.otlp_endpoint.as_deref(),
+                    Some("http://otel-collector:4317")
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_from_env_dry_run() {
+        with_env_vars(
+            &[
+                ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
+                ("DRY_RUN", Some("true")),
+            ],
+            || {
+                let config = Config::from_env().unwrap();
+                assert!(config.dry_run);
+            },
+        );
+    }
+
+    #[test]
+    fn test_from_env_admin_token() {
+        with_env_vars(
+            &[
+                ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
+                ("ADMIN_TOKEN", Some("s3cr3t")),
+            ],
+            || {
+                let config = Config::from_env().unwrap();
+                assert_eq!(config.admin_token.as_deref(), Some("s3cr3t"));
+            },
+        );
+    }
+
+    #[test]
+    fn test_from_env_paused() {
+        with_env_vars(
+            &[
+                ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
+                ("PAUSED", Some("true")),
+            ],
+            || {
+                let config = Config::from_env().unwrap();
+                assert!(config.paused);
+            },
+        );
+    }
+
+    #[test]
+    fn test_from_env_persist_state() {
+        with_env_vars(
+            &[
+                ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
+                ("PERSIST_STATE", Some("true")),
+                ("STATE_CONFIG_MAP", Some("outrider-state")),
+                ("STATE_CONFIG_MAP_SHARDS", Some("2")),
+                ("POD_NAMESPACE", Some("outrider-system")),
+            ],
+            || {
+                let config = Config::from_env().unwrap();
+                let state = config.state_config_map.unwrap();
+                assert_eq!(
+                    state,
+                    StateConfigMapConfig {
+                        name: "outrider-state".to_string(),
+                        namespace: "outrider-system".to_string(),
+                        shards: 2,
+                    }
+                );
+                assert_eq!(
+                    state.config_map_names(),
+                    vec!["outrider-state-0", "outrider-state-1"]
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_from_env_persist_state_requires_pod_namespace() {
+        with_env_vars(
+            &[
+                ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
+                ("PERSIST_STATE", Some("true")),
+                ("POD_NAMESPACE", None),
+            ],
+            || {
+                let result = Config::from_env();
+                assert!(result.unwrap_err().to_string().contains("POD_NAMESPACE"));
+            },
+        );
+    }
+
+    #[test]
+    fn test_from_env_invalid_log_format() {
+        with_env_vars(
+            &[
+                ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
+                ("LOG_FORMAT", Some("xml")),
+            ],
+            || {
+                let result = Config::from_env();
+                assert!(result.unwrap_err().to_string().contains("LOG_FORMAT"));
+            },
+        );
+    }
+
+    #[test]
+    fn test_config_file_settings() {
+        let file = parse_config_file(
+            r#"
+default_target_namespace: from-file
+max_concurrent_syncs: 3
+sync_debounce_ms: 0
+watch_namespaces: [team-a, team-b]
+log_format: json
+"#,
+        )
+        .unwrap();
+
+        with_env_vars(
+            &[
+                ("DEFAULT_TARGET_NAMESPACE", None),
+                ("MAX_CONCURRENT_SYNCS", None),
+                ("SYNC_DEBOUNCE_MS", None),
+                ("WATCH_NAMESPACES", None),
+                ("LOG_FORMAT", None),
+            ],
+            || {
+                let config = Config::from_sources(file).unwrap();
+                assert_eq!(config.default_target_namespace, "from-file");
+                assert_eq!(config.max_concurrent_syncs, 3);
+                assert_eq!(config.debounce, Duration::ZERO);
+                assert_eq!(config.watch_namespaces, vec!["team-a", "team-b"]);
+                assert_eq!(config.log_format, LogFormat::Json);
+                assert_eq!(
+                    config.sync_timeout,
+                    Duration::from_secs(DEFAULT_SYNC_TIMEOUT_SECS)
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_env_overrides_config_file() {
+        let file =
+            parse_config_file("default_target_namespace: from-file\nmax_concurrent_syncs: 3")
+                .unwrap();
+
+        with_env_vars(
+            &[
+                ("DEFAULT_TARGET_NAMESPACE", Some("from-env")),
+                ("MAX_CONCURRENT_SYNCS", Some("5")),
+            ],
+            || {
+                let config = Config::from_sources(file).unwrap();
+                assert_eq!(config.default_target_namespace, "from-env");
+                assert_eq!(config.max_concurrent_syncs, 5);
+            },
+        );
+    }
+
+    #[test]
+    fn test_invalid_config_file() {
+        assert!(parse_config_file("max_concurrent_syncs: lots").is_err());
+        // Typos are reported instead of silently ignored
+        assert!(parse_config_file("max_concurent_syncs: 3").is_err());
+        assert!(parse_config_file("").is_ok());
+
+        let file = parse_config_file("max_concurrent_syncs: 0").unwrap();
+        with_env_vars(
+            &[
+                ("DEFAULT_TARGET_NAMESPACE", Some("my-namespace")),
+                ("MAX_CONCURRENT_SYNCS", None),
+            ],
+            || {
+                let result = Config::from_sources(file);
+                assert!(
+                    result
+                        .unwrap_err()
+                        .to_string()
+                        .contains("max_concurrent_syncs")
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_reload_keeps_restart_only_settings() {
+        let current = with_env_vars(
+            &[("DEFAULT_TARGET_NAMESPACE", Some("my-namespace"))],
+            Config::from_env,
+        )
+        .unwrap();
+        let mut new = current.clone();
+        new.default_target_namespace = "other-namespace".to_string();
+        new.max_concurrent_syncs = 2;
+        new.watch_namespaces = vec!["team-a".to_string()];
+        new.paused = true;
+
+        let (reloaded, ignored) = current.reload(new);
+
+        assert_eq!(reloaded.default_target_namespace, "other-namespace");
+        assert_eq!(reloaded.max_concurrent_syncs, 2);
+        assert!(reloaded.paused);
+        assert!(reloaded.watch_namespaces.is_empty());
+        assert_eq!(ignored, vec!["WATCH_NAMESPACES"]);
+    }
+}
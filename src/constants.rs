@@ -1,12 +1,31 @@
 // Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
 // SPDX-License-Identifier: Apache-2.0
 
+/// Name Rancher gives the manager cluster
+pub const LOCAL_CLUSTER: &str = "local";
+
 /// Kubernetes annotation keys used by Outrider
 pub mod annotations {
     /// When set to "true", enables secret syncing for this secret
     pub const ENABLED: &str = "outrider.geeko.me/enabled";
     /// Target namespace in downstream clusters (optional)
     pub const NAMESPACE: &str = "outrider.geeko.me/namespace";
+    /// Hash of the content Outrider applied, recorded on downstream secrets
+    pub const CONTENT_HASH: &str = "outrider.geeko.me/content-hash";
+    /// Set or change to any value, e.g. the current time, on a source secret or
+    /// a Rancher cluster to redo its full sync
+    pub const RESYNC_REQUESTED_AT: &str = "outrider.geeko.me/resync-requested-at";
+    /// When set to "true" on a Rancher cluster, secrets are queued but not written to it
+    pub const PAUSED: &str = "outrider.geeko.me/paused";
+}
+
+/// Kubernetes label keys used by Outrider
+pub mod labels {
+    /// When set to "true", enables secret syncing for this secret.
+    /// Unlike the annotation, the label can be used as a server-side watch selector.
+    pub const ENABLED: &str = "outrider.geeko.me/enabled";
+    /// Label selector matching secrets that opted in through the label
+    pub const ENABLED_SELECTOR: &str = "outrider.geeko.me/enabled=true";
 }
 
 /// The operator name used for server-side apply
@@ -19,3 +38,74 @@ pub mod crd {
     /// Maximum polling interval in seconds (exponential backoff cap)
     pub const POLL_MAX_INTERVAL_SECS: u64 = 60;
 }
+
+/// Sync manager configuration
+pub mod sync {
+    /// Default number of clusters synced to concurrently
+    pub const DEFAULT_MAX_CONCURRENT_SYNCS: usize = 10;
+    /// Default timeout in seconds for a single secret copy to a cluster
+    pub const DEFAULT_SYNC_TIMEOUT_SECS: u64 = 30;
+    /// Default window in milliseconds during which events for the same object are coalesced
+    pub const DEFAULT_DEBOUNCE_MS: u64 = 500;
+    /// Default time in seconds to finish in-flight syncs on shutdown.
+    /// Stays below the default Kubernetes termination grace period of 30 seconds.
+    pub const DEFAULT_SHUTDOWN_TIMEOUT_SECS: u64 = 25;
+    /// Number of enabled secrets fetched concurrently for a full sync
+    pub const MAX_CONCURRENT_FETCHES: usize = 10;
+}
+
+/// Retry policy for failed secret copies
+pub mod retry {
+    /// Delay in seconds before the first retry
+    pub const RETRY_BASE_DELAY_SECS: u64 = 2;
+    /// Maximum delay in seconds between retries (exponential backoff cap)
+    pub const RETRY_MAX_DELAY_SECS: u64 = 300;
+}
+
+/// Permission checks with SelfSubjectAccessReviews
+pub mod access {
+    /// Interval in seconds at which a cluster missing permissions is checked again
+    pub const RECHECK_INTERVAL_SECS: u64 = 60;
+}
+
+/// HTTP server for metrics and probes
+pub mod http {
+    /// Default port the HTTP server listens on
+    pub const DEFAULT_PORT: u16 = 8080;
+}
+
+/// Liveness checks
+pub mod health {
+    /// Interval in seconds at which the sync manager loop reports a heartbeat
+    pub const HEARTBEAT_INTERVAL_SECS: u64 = 30;
+    /// Time in seconds without progress, or with only errors, after which a component is stalled
+    pub const STALL_THRESHOLD_SECS: u64 = 300;
+}
+
+/// Lease-based leader election, using the same timings as client-go
+pub mod leader {
+    /// Default name of the Lease object
+    pub const DEFAULT_LEASE_NAME: &str = "outrider";
+    /// Time in seconds after its last renewal that other replicas may take over the lease
+    pub const LEASE_DURATION_SECS: u64 = 15;
+    /// Time in seconds the leader keeps leading while it fails to renew the lease
+    pub const RENEW_DEADLINE_SECS: u64 = 10;
+    /// Interval in seconds between attempts to acquire or renew the lease
+    pub const RETRY_PERIOD_SECS: u64 = 2;
+}
+
+/// Sync state persisted across restarts
+pub mod state {
+    /// Default name prefix of the ConfigMaps holding the sync state
+    pub const DEFAULT_CONFIG_MAP_NAME: &str = "outrider-sync-state";
+    /// Default number of ConfigMaps the sync state is spread over
+    pub const DEFAULT_SHARDS: usize = 8;
+    /// Interval in seconds at which changes to the sync state are saved
+    pub const SAVE_INTERVAL_SECS: u64 = 10;
+}
+
+/// Reloading of the config file
+pub mod reload {
+    /// Interval in seconds at which the config file is checked for changes
+    pub const POLL_INTERVAL_SECS: u64 = 10;
+}
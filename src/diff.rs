@@ -0,0 +1,358 @@
+// Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Comparison of enabled source secrets with their downstream copies.
+//! Only secret names and data keys are reported, never values.
+
+use crate::config::Config;
+use crate::constants::OPERATOR_NAME;
+use crate::error::Result;
+use crate::kubernetes::create_downstream_client;
+use crate::plan::table;
+use crate::sync::secrets::{
+    create_downstream_secret, describe_changes, get_target_namespace, recorded_content_hash,
+    secret_key,
+};
+use crate::sync::{get_enabled_secrets, get_ready_clusters};
+use crate::types::cluster::Cluster;
+use futures::{StreamExt, stream};
+use k8s_openapi::api::core::v1::Secret;
+use kube::{Api, Client, ResourceExt, api::ListParams};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use tokio::time::timeout;
+
+/// How a downstream secret differs from what syncing would produce
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// An enabled source secret has no downstream copy
+    Missing,
+    /// The content hash of the downstream copy does not match its source
+    Stale,
+    /// Carries the content hash Outrider records, but no enabled source secret
+    /// maps to it anymore
+    Extra,
+    /// A secret with the same name exists, but was not written by Outrider
+    Conflicting,
+    /// The downstream cluster could not be read
+    Error,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Missing => write!(f, "missing"),
+            Status::Stale => write!(f, "stale"),
+            Status::Extra => write!(f, "extra"),
+            Status::Conflicting => write!(f, "conflicting"),
+            Status::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A downstream secret that is not in sync
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Difference {
+    pub cluster: String,
+    /// Downstream secret as `namespace/name`, empty for cluster errors
+    pub secret: String,
+    pub status: Status,
+    pub detail: String,
+}
+
+/// Compare every enabled secret with its copy in every ready cluster, and
+/// find the copies whose source is gone
+pub async fn diff(client: &Client, config: &Config) -> Result<Vec<Difference>> {
+    let secrets = get_enabled_secrets(client, config).await?;
+    let clusters = get_ready_clusters(client).await?;
+
+    let desired: Vec<(String, Secret)> = secrets
+        .iter()
+        .map(|secret| {
+            let target = get_target_namespace(secret, config);
+            (secret_key(secret), create_downstream_secret(secret, target))
+        })
+        .collect();
+
+    let mut differences = diff_clusters(client, config, &clusters, &desired).await;
+    differences.sort_by(|a, b| (&a.cluster, &a.secret).cmp(&(&b.cluster, &b.secret)));
+    Ok(differences)
+}
+
+/// Compare the clusters concurrently, at most `max_concurrent_syncs` at a time.
+/// A cluster that does not answer within the sync timeout is reported as an error.
+async fn diff_clusters(
+    client: &Client,
+    config: &Config,
+    clusters: &[Cluster],
+    desired: &[(String, Secret)],
+) -> Vec<Difference> {
+    let per_cluster: Vec<Vec<Difference>> = stream::iter(clusters)
+        .map(|cluster| async move {
+            match timeout(
+                config.sync_timeout,
+                diff_cluster(client, config, cluster, desired),
+            )
+            .await
+            {
+                Ok(differences) => differences,
+                Err(_) => vec![Difference {
+                    cluster: cluster.name_any(),
+                    secret: String::new(),
+                    status: Status::Error,
+                    detail: format!("no response within {:?}", config.sync_timeout),
+                }],
+            }
+        })
+        .buffer_unordered(config.max_concurrent_syncs)
+        .collect()
+        .await;
+
+    per_cluster.into_iter().flatten().collect()
+}
+
+async fn diff_cluster(
+    client: &Client,
+    config: &Config,
+    cluster: &Cluster,
+    desired: &[(String, Secret)],
+) -> Vec<Difference> {
+    let existing = match create_downstream_client(client, cluster, config).await {
+        Ok(downstream) => Api::<Secret>::all(downstream)
+            .list(&ListParams::default())
+            .await
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+
+    match existing {
+        Ok(existing) => compare(&cluster.name_any(), desired, &existing.items),
+        Err(e) => vec![Difference {
+            cluster: cluster.name_any(),
+            secret: String::new(),
+            status: Status::Error,
+            detail: e,
+        }],
+    }
+}
+
+/// Compare the desired downstream secrets, keyed by their source, with the
+/// secrets found in a cluster. Only secrets carrying the content hash Outrider
+/// records, or named like a copy, are considered; others are left out.
+fn compare(cluster: &str, desired: &[(String, Secret)], existing: &[Secret]) -> Vec<Difference> {
+    let desired_keys: BTreeSet<String> = desired.iter().map(|(_, s)| secret_key(s)).collect();
+    let existing: BTreeMap<String, &Secret> = existing
+        .iter()
+        .map(|s| (secret_key(s), s))
+        .filter(|(key, s)| recorded_content_hash(s).is_some() || desired_keys.contains(key))
+        .collect();
+    let difference = |secret: &str, status, detail: String| Difference {
+        cluster: cluster.to_string(),
+        secret: secret.to_string(),
+        status,
+        detail,
+    };
+
+    let mut differences = Vec::new();
+    for (source, secret) in desired {
+        let key = secret_key(secret);
+        match existing.get(&key) {
+            None => differences.push(difference(
+                &key,
+                Status::Missing,
+                format!("from {}", source),
+            )),
+            Some(current) if !is_managed(current) => differences.push(difference(
+                &key,
+                Status::Conflicting,
+                format!(
+                    "not managed by Outrider, would be overwritten by {}",
+                    source
+                ),
+            )),
+            Some(current) if recorded_content_hash(current) != recorded_content_hash(secret) => {
+                differences.push(difference(
+                    &key,
+                    Status::Stale,
+                    describe_changes(Some(current), secret),
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+
+    for key in existing.keys() {
+        if !desired_keys.contains(key) {
+            differences.push(difference(
+                key,
+                Status::Extra,
+                "no enabled source secret".to_string(),
+            ));
+        }
+    }
+    differences
+}
+
+/// Whether Outrider wrote the secret, either recording its content hash or
+/// owning fields through server-side apply
+fn is_managed(secret: &Secret) -> bool {
+    recorded_content_hash(secret).is_some()
+        || secret
+            .metadata
+            .managed_fields
+            .iter()
+            .flatten()
+            .any(|entry| entry.manager.as_deref() == Some(OPERATOR_NAME))
+}
+
+/// Render the differences as a table, followed by a summary
+pub fn render(differences: &[Difference]) -> String {
+    let mut out = String::new();
+    if !differences.is_empty() {
+        let rows: Vec<Vec<String>> = differences
+            .iter()
+            .map(|d| {
+                vec![
+                    d.cluster.clone(),
+                    d.secret.clone(),
+                    d.status.to_string(),
+                    d.detail.clone(),
+                ]
+            })
+            .collect();
+        out.push_str(&table(&["CLUSTER", "SECRET", "STATUS", "DETAIL"], &rows));
+        out.push('\n');
+    }
+
+    let count = |status| differences.iter().filter(|d| d.status == status).count();
+    out.push_str(&format!(
+        "{} missing, {} stale, {} extra, {} conflicting, {} failed\n",
+        count(Status::Missing),
+        count(Status::Stale),
+        count(Status::Extra),
+        count(Status::Conflicting),
+        count(Status::Error),
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::annotations;
+    use crate::test_utils::{MockService, make_cluster, make_secret, test_config};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ManagedFieldsEntry;
+    use std::time::Duration;
+
+    fn copy(name: &str, password: &str) -> (String, Secret) {
+        let secret = make_secret("fleet-default", name, &[("password", password)]);
+        (
+            secret_key(&secret),
+            create_downstream_secret(&secret, "target"),
+        )
+    }
+
+    #[test]
+    fn test_compare() {
+        let desired = vec![
+            copy("missing", "s3cr3t"),
+            copy("stale", "new-s3cr3t"),
+            copy("in-sync", "s3cr3t"),
+            copy("conflicting", "s3cr3t"),
+        ];
+        let mut conflicting = desired[3].1.clone();
+        conflicting
+            .annotations_mut()
+            .remove(annotations::CONTENT_HASH);
+        let mut unmanaged = desired[0].1.clone();
+        unmanaged.metadata.name = Some("unmanaged".to_string());
+        unmanaged
+            .annotations_mut()
+            .remove(annotations::CONTENT_HASH);
+        let mut extra = desired[0].1.clone();
+        extra.metadata.name = Some("extra".to_string());
+        // Not reported as extra without the content hash
+        let mut unrelated = unmanaged.clone();
+        unrelated.metadata.name = Some("unrelated".to_string());
+        unrelated.metadata.managed_fields = Some(vec![ManagedFieldsEntry {
+            manager: Some(OPERATOR_NAME.to_string()),
+            ..Default::default()
+        }]);
+        let existing = vec![
+            copy("stale", "old-s3cr3t").1,
+            desired[2].1.clone(),
+            conflicting,
+            unmanaged,
+            unrelated,
+            extra,
+        ];
+
+        let differences = compare("downstream", &desired, &existing);
+
+        let found: Vec<(&str, Status)> = differences
+            .iter()
+            .map(|d| (d.secret.as_str(), d.status))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("target/missing", Status::Missing),
+                ("target/stale", Status::Stale),
+                ("target/conflicting", Status::Conflicting),
+                ("target/extra", Status::Extra),
+            ]
+        );
+        assert_eq!(differences[0].detail, "from fleet-default/missing");
+        assert!(differences[1].detail.starts_with("data [~password]"));
+    }
+
+    #[tokio::test]
+    async fn test_unresponsive_cluster_is_reported_as_error() {
+        let mock = MockService::new()
+            .hang_on_get("/api/v1/namespaces/fleet-default/secrets/unreachable-kubeconfig");
+        let config = Config {
+            sync_timeout: Duration::from_millis(50),
+            ..test_config()
+        };
+        let clusters = [make_cluster("unreachable", true)];
+
+        let differences = diff_clusters(
+            &mock.into_client(),
+            &config,
+            &clusters,
+            &[copy("creds", "s3cr3t")],
+        )
+        .await;
+
+        assert_eq!(
+            differences,
+            vec![Difference {
+                cluster: "unreachable".to_string(),
+                secret: String::new(),
+                status: Status::Error,
+                detail: "no response within 50ms".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_render_redacts_values() {
+        let desired = vec![copy("creds", "new-s3cr3t")];
+        let existing = vec![copy("creds", "old-s3cr3t").1];
+
+        let out = render(&compare("downstream", &desired, &existing));
+
+        assert!(out.starts_with("CLUSTER     SECRET        STATUS  DETAIL\n"));
+        assert!(out.contains("downstream  target/creds  stale   data [~password]"));
+        assert!(out.ends_with("0 missing, 1 stale, 0 extra, 0 conflicting, 0 failed\n"));
+        assert!(!out.contains("s3cr3t"));
+    }
+
+    #[test]
+    fn test_render_no_differences() {
+        assert_eq!(
+            render(&[]),
+            "0 missing, 0 stale, 0 extra, 0 conflicting, 0 failed\n"
+        );
+    }
+}
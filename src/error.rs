@@ -10,17 +10,126 @@ pub enum OutriderError {
     #[error("Failed to parse kubeconfig: {0}")]
     KubeconfigError(String),
 
+    #[error("Kubeconfig not available: {0}")]
+    KubeconfigUnavailable(String),
+
     #[error("Cluster not ready: {0}")]
     ClusterNotReady(String),
 
     #[error("Secret copy failed: {0}")]
     SecretCopyError(String),
 
-    #[error("Namespace creation failed: {0}")]
-    NamespaceError(String),
+    #[error("Namespace creation failed for {namespace}: {source}")]
+    NamespaceError {
+        namespace: String,
+        #[source]
+        source: kube::Error,
+    },
+
+    #[error("Sync timed out: {0}")]
+    SyncTimeout(String),
 
     #[error("Invalid annotation: {0}")]
     InvalidAnnotation(String),
+
+    #[error("Missing permissions: {0}")]
+    MissingPermissions(String),
+}
+
+impl OutriderError {
+    /// Whether the operation that produced this error may succeed when retried.
+    /// Permanent errors (invalid kubeconfigs, forbidden or invalid requests)
+    /// are not retried until the source secret or cluster changes. Missing
+    /// permissions are retried, as they are checked again once in a while.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            OutriderError::KubeError(e) => is_retryable_kube_error(e),
+            OutriderError::NamespaceError { source, .. } => is_retryable_kube_error(source),
+            OutriderError::KubeconfigUnavailable(_)
+            | OutriderError::ClusterNotReady(_)
+            | OutriderError::SyncTimeout(_)
+            | OutriderError::MissingPermissions(_) => true,
+            OutriderError::KubeconfigError(_)
+            | OutriderError::SecretCopyError(_)
+            | OutriderError::InvalidAnnotation(_) => false,
+        }
+    }
+}
+
+/// API errors are retryable for timeouts, conflicts, throttling and server errors,
+/// and for 401, as the token of a downstream cluster may be in the middle of
+/// being rotated. Transport level errors are always considered retryable.
+fn is_retryable_kube_error(error: &kube::Error) -> bool {
+    match error {
+        kube::Error::Api(response) => matches!(response.code, 401 | 408 | 409 | 429 | 500..),
+        kube::Error::SerdeError(_) | kube::Error::BuildRequest(_) | kube::Error::TlsRequired => {
+            false
+        }
+        _ => true,
+    }
 }
 
-pub type Result<T> = std::result::Result<T, OutriderError>;
\ No newline at end of file
+pub type Result<T> = std::result::Result<T, OutriderError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kube::core::ErrorResponse;
+
+    fn api_error(code: u16) -> kube::Error {
+        kube::Error::Api(ErrorResponse {
+            status: "Failure".to_string(),
+            message: "test".to_string(),
+            reason: "Test".to_string(),
+            code,
+        })
+    }
+
+    #[test]
+    fn test_server_errors_are_retryable() {
+        for code in [401, 408, 409, 429, 500, 503] {
+            assert!(
+                OutriderError::KubeError(api_error(code)).is_retryable(),
+                "expected {} to be retryable",
+                code
+            );
+        }
+    }
+
+    #[test]
+    fn test_client_errors_are_permanent() {
+        for code in [400, 403, 404, 422] {
+            assert!(
+                !OutriderError::KubeError(api_error(code)).is_retryable(),
+                "expected {} to be permanent",
+                code
+            );
+        }
+    }
+
+    #[test]
+    fn test_namespace_error_uses_source() {
+        let retryable = OutriderError::NamespaceError {
+            namespace: "ns".to_string(),
+            source: api_error(503),
+        };
+        let permanent = OutriderError::NamespaceError {
+            namespace: "ns".to_string(),
+            source: api_error(403),
+        };
+
+        assert!(retryable.is_retryable());
+        assert!(!permanent.is_retryable());
+    }
+
+    #[test]
+    fn test_kubeconfig_errors() {
+        assert!(OutriderError::KubeconfigUnavailable("missing".to_string()).is_retryable());
+        assert!(!OutriderError::KubeconfigError("invalid".to_string()).is_retryable());
+    }
+
+    #[test]
+    fn test_missing_permissions_are_retryable() {
+        assert!(OutriderError::MissingPermissions("patch secrets".to_string()).is_retryable());
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Liveness and readiness state backing the `/healthz` and `/readyz` probes.
+
+use crate::constants::health::STALL_THRESHOLD_SECS;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Last reported state of a long-running component
+#[derive(Debug, Default)]
+struct Component {
+    /// Last heartbeat, for components that report one periodically
+    last_beat: Option<Instant>,
+    /// Since when the component has only seen errors
+    failing_since: Option<Instant>,
+    exited: bool,
+}
+
+/// Shared, cheaply cloneable operator health.
+/// Components that have not reported anything yet are considered healthy.
+#[derive(Clone, Default)]
+pub struct Health {
+    crd_ready: Arc<AtomicBool>,
+    initial_sync_done: Arc<AtomicBool>,
+    components: Arc<Mutex<BTreeMap<String, Component>>>,
+}
+
+impl Health {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_crd_ready(&self) {
+        self.crd_ready.store(true, Ordering::SeqCst);
+    }
+
+    pub fn set_initial_sync_done(&self) {
+        self.initial_sync_done.store(true, Ordering::SeqCst);
+    }
+
+    /// Ready once the Cluster CRD is available and the initial sync has been queued,
+    /// or on a standby replica, once its caches are loaded
+    pub fn is_ready(&self) -> bool {
+        self.crd_ready.load(Ordering::SeqCst) && self.initial_sync_done.load(Ordering::SeqCst)
+    }
+
+    /// Record a periodic heartbeat. The component is considered stalled when
+    /// no heartbeat arrives within the stall threshold.
+    pub fn beat(&self, component: &str) {
+        self.update(component, |c| c.last_beat = Some(Instant::now()));
+    }
+
+    /// Record that a component made progress, e.g. received a watch event
+    pub fn succeeded(&self, component: &str) {
+        self.update(component, |c| c.failing_since = None);
+    }
+
+    /// Record an error. The component is considered stalled when it keeps
+    /// failing for longer than the stall threshold.
+    pub fn failed(&self, component: &str) {
+        self.update(component, |c| {
+            c.failing_since.get_or_insert_with(Instant::now);
+        });
+    }
+
+    /// Record that a component stopped, which it never should
+    pub fn exited(&self, component: &str) {
+        self.update(component, |c| c.exited = true);
+    }
+
+    /// Check that no component has exited or stalled
+    pub fn check_live(&self) -> Result<(), String> {
+        self.check_live_at(Instant::now())
+    }
+
+    fn check_live_at(&self, now: Instant) -> Result<(), String> {
+        let threshold = Duration::from_secs(STALL_THRESHOLD_SECS);
+        let stale = |at: Option<Instant>| at.is_some_and(|at| now.duration_since(at) > threshold);
+
+        for (name, component) in self.components.lock().unwrap().iter() {
+            if component.exited {
+                return Err(format!("{} has exited", name));
+            }
+            if stale(component.last_beat) {
+                return Err(format!(
+                    "{} has not made progress for {:?}",
+                    name, threshold
+                ));
+            }
+            if stale(component.failing_since) {
+                return Err(format!("{} has been failing for {:?}", name, threshold));
+            }
+        }
+        Ok(())
+    }
+
+    fn update(&self, component: &str, f: impl FnOnce(&mut Component)) {
+        let mut components = self.components.lock().unwrap();
+        f(components.entry(component.to_string()).or_default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ready_requires_crd_and_initial_sync() {
+        let health = Health::new();
+        assert!(!health.is_ready());
+
+        health.set_crd_ready();
+        assert!(!health.is_ready());
+
+        health.set_initial_sync_done();
+        assert!(health.is_ready());
+    }
+
+    #[test]
+    fn test_live_until_component_exits() {
+        let health = Health::new();
+        health.beat("sync-manager");
+        assert!(health.check_live().is_ok());
+
+        health.exited("sync-manager");
+        assert!(health.check_live().unwrap_err().contains("sync-manager"));
+    }
+
+    #[test]
+    fn test_missing_heartbeat_is_stalled() {
+        let health = Health::new();
+        health.beat("sync-manager");
+        let later = Instant::now() + Duration::from_secs(STALL_THRESHOLD_SECS + 1);
+
+        assert!(health.check_live_at(later).is_err());
+    }
+
+    #[test]
+    fn test_recovered_failure_is_live() {
+        let health = Health::new();
+        let later = Instant::now() + Duration::from_secs(STALL_THRESHOLD_SECS + 1);
+
+        health.failed("clusters");
+        assert!(health.check_live_at(later).is_err());
+
+        health.succeeded("clusters");
+        assert!(health.check_live_at(later).is_ok());
+    }
+}
@@ -0,0 +1,336 @@
+// Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Permission checks with SelfSubjectAccessReviews, so missing RBAC rules are
+//! reported up front instead of failing every copy.
+
+use crate::config::Config;
+use crate::constants::LOCAL_CLUSTER;
+use crate::constants::access::RECHECK_INTERVAL_SECS;
+use crate::error::Result;
+use crate::sync::SyncManagerHandle;
+use crate::types::cluster::Cluster;
+use futures::future::try_join_all;
+use k8s_openapi::api::authorization::v1::{
+    ResourceAttributes, SelfSubjectAccessReview, SelfSubjectAccessReviewSpec,
+};
+use kube::{Api, Client, api::PostParams};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::time::Duration;
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, warn};
+
+/// An API operation Outrider needs to be allowed to perform
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Permission {
+    pub verb: &'static str,
+    /// API group, empty for the core group
+    pub group: &'static str,
+    pub resource: &'static str,
+    /// None for cluster-scoped resources, or for namespaced resources in all namespaces
+    pub namespace: Option<String>,
+    pub name: Option<String>,
+}
+
+impl Permission {
+    pub fn new(verb: &'static str, group: &'static str, resource: &'static str) -> Self {
+        Self {
+            verb,
+            group,
+            resource,
+            namespace: None,
+            name: None,
+        }
+    }
+
+    /// Restrict the permission to a namespace
+    pub fn within(mut self, namespace: &str) -> Self {
+        self.namespace = Some(namespace.to_string());
+        self
+    }
+
+    /// Restrict the permission to a single object
+    pub fn named(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.verb, self.resource)?;
+        if !self.group.is_empty() {
+            write!(f, ".{}", self.group)?;
+        }
+        if let Some(name) = &self.name {
+            write!(f, " '{}'", name)?;
+        }
+        match &self.namespace {
+            Some(namespace) => write!(f, " in namespace {}", namespace),
+            None => write!(f, " cluster-wide"),
+        }
+    }
+}
+
+/// Permissions needed in the manager cluster to watch secrets and clusters,
+/// and to hold the Lease and sync state ConfigMaps when enabled
+pub fn manager_permissions(config: &Config) -> Vec<Permission> {
+    let mut required = Vec::new();
+    for verb in ["get", "list", "watch"] {
+        if config.watch_namespaces.is_empty() {
+            required.push(Permission::new(verb, "", "secrets"));
+        }
+        for namespace in &config.watch_namespaces {
+            required.push(Permission::new(verb, "", "secrets").within(namespace));
+        }
+        required.push(Permission::new(verb, "provisioning.cattle.io", "clusters"));
+    }
+    if let Some(lease) = &config.leader_election {
+        for verb in ["get", "create", "update"] {
+            required.push(
+                Permission::new(verb, "coordination.k8s.io", "leases").within(&lease.namespace),
+            );
+        }
+    }
+    if let Some(state) = &config.state_config_map {
+        required.push(Permission::new("create", "", "configmaps").within(&state.namespace));
+        for name in state.config_map_names() {
+            for verb in ["get", "patch"] {
+                required.push(
+                    Permission::new(verb, "", "configmaps")
+                        .within(&state.namespace)
+                        .named(&name),
+                );
+            }
+        }
+    }
+    required
+}
+
+/// Permission needed in the manager cluster to read the kubeconfig of a cluster
+pub fn kubeconfig_permission(cluster: &Cluster) -> Permission {
+    Permission::new("get", "", "secrets")
+        .within(&cluster.kubeconfig_secret_namespace())
+        .named(&cluster.kubeconfig_secret_name())
+}
+
+/// Permissions the kubeconfig of a downstream cluster needs to apply secrets in
+/// each of the target `namespaces`, and to create namespaces when
+/// `create_namespaces` is set because some of them do not exist yet
+pub fn downstream_permissions(
+    namespaces: &BTreeSet<String>,
+    create_namespaces: bool,
+) -> Vec<Permission> {
+    let mut required = vec![Permission::new("get", "", "namespaces")];
+    if create_namespaces {
+        required.push(Permission::new("create", "", "namespaces"));
+    }
+    for namespace in namespaces {
+        for verb in ["get", "create", "patch"] {
+            required.push(Permission::new(verb, "", "secrets").within(namespace));
+        }
+    }
+    required
+}
+
+/// The permissions in `required` that the client's user is not allowed
+pub async fn missing_permissions(
+    client: &Client,
+    required: &[Permission],
+) -> Result<Vec<Permission>> {
+    let reviews: Api<SelfSubjectAccessReview> = Api::all(client.clone());
+    let allowed = try_join_all(required.iter().map(|permission| {
+        let reviews = reviews.clone();
+        async move {
+            let review = reviews
+                .create(&PostParams::default(), &access_review(permission))
+                .await?;
+            Ok::<_, kube::Error>(review.status.is_some_and(|status| status.allowed))
+        }
+    }))
+    .await?;
+
+    Ok(required
+        .iter()
+        .zip(allowed)
+        .filter(|(_, allowed)| !allowed)
+        .map(|(permission, _)| permission.clone())
+        .collect())
+}
+
+/// Check the permissions needed in the manager cluster now and again every
+/// recheck interval until `shutdown` is cancelled, recording what is missing
+/// in the sync status and metrics under the manager cluster's name. A check
+/// that could not complete is tried again after the same interval. Each
+/// downstream cluster is checked by its worker.
+pub async fn watch_manager_access(
+    client: Client,
+    config: &Config,
+    handle: SyncManagerHandle,
+    shutdown: CancellationToken,
+) -> anyhow::Result<()> {
+    let required = manager_permissions(config);
+    loop {
+        match missing_permissions(&client, &required).await {
+            Ok(missing) => {
+                for permission in &missing {
+                    error!("Missing permission in the manager cluster: {}", permission);
+                }
+                handle
+                    .metrics()
+                    .set_missing_permissions(LOCAL_CLUSTER, missing.len());
+                handle.status().set_missing_permissions(
+                    LOCAL_CLUSTER,
+                    missing.iter().map(ToString::to_string).collect(),
+                );
+            }
+            Err(e) => warn!(
+                "Could not check permissions in the manager cluster, retrying in {}s: {}",
+                RECHECK_INTERVAL_SECS, e
+            ),
+        }
+
+        tokio::select! {
+            _ = shutdown.cancelled() => return Ok(()),
+            _ = sleep(Duration::from_secs(RECHECK_INTERVAL_SECS)) => {}
+        }
+    }
+}
+
+fn access_review(permission: &Permission) -> SelfSubjectAccessReview {
+    SelfSubjectAccessReview {
+        spec: SelfSubjectAccessReviewSpec {
+            resource_attributes: Some(ResourceAttributes {
+                verb: Some(permission.verb.to_string()),
+                group: Some(permission.group.to_string()),
+                resource: Some(permission.resource.to_string()),
+                namespace: permission.namespace.clone(),
+                name: permission.name.clone(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{SecretStores, SyncManager};
+    use crate::test_utils::{MockService, test_config};
+    use kube::runtime::reflector;
+    use tokio::sync::watch;
+
+    const REVIEWS: &str = "/apis/authorization.k8s.io/v1/selfsubjectaccessreviews";
+
+    fn review(allowed: bool) -> String {
+        serde_json::json!({
+            "apiVersion": "authorization.k8s.io/v1",
+            "kind": "SelfSubjectAccessReview",
+            "spec": {},
+            "status": {"allowed": allowed}
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            Permission::new("list", "", "secrets").to_string(),
+            "list secrets cluster-wide"
+        );
+        assert_eq!(
+            Permission::new("get", "", "secrets")
+                .within("fleet-default")
+                .named("downstream-kubeconfig")
+                .to_string(),
+            "get secrets 'downstream-kubeconfig' in namespace fleet-default"
+        );
+        assert_eq!(
+            Permission::new("watch", "provisioning.cattle.io", "clusters").to_string(),
+            "watch clusters.provisioning.cattle.io cluster-wide"
+        );
+    }
+
+    #[test]
+    fn test_downstream_permissions_cover_target_namespaces() {
+        let namespaces = BTreeSet::from(["cattle-global-data".to_string(), "team-a".to_string()]);
+
+        let required = downstream_permissions(&namespaces, true);
+
+        assert_eq!(required.len(), 8);
+        for namespace in &namespaces {
+            assert!(required.contains(&Permission::new("patch", "", "secrets").within(namespace)));
+        }
+    }
+
+    #[test]
+    fn test_downstream_permissions_without_namespace_create() {
+        let namespaces = BTreeSet::from(["team-a".to_string()]);
+
+        let required = downstream_permissions(&namespaces, false);
+
+        assert_eq!(required.len(), 4);
+        assert!(!required.contains(&Permission::new("create", "", "namespaces")));
+    }
+
+    #[tokio::test]
+    async fn test_missing_permissions() {
+        let mock = MockService::new().on_post(REVIEWS, 201, &review(false));
+        let required = vec![
+            Permission::new("create", "", "namespaces"),
+            Permission::new("patch", "", "secrets").within("default"),
+        ];
+
+        let missing = missing_permissions(&mock.clone().into_client(), &required)
+            .await
+            .unwrap();
+
+        assert_eq!(missing, required);
+        assert_eq!(mock.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_all_permissions_allowed() {
+        let mock = MockService::new().on_post(REVIEWS, 201, &review(true));
+        let required = vec![Permission::new("create", "", "namespaces")];
+
+        let missing = missing_permissions(&mock.into_client(), &required)
+            .await
+            .unwrap();
+
+        assert!(missing.is_empty());
+    }
+
+    fn make_handle() -> SyncManagerHandle {
+        let config = test_config();
+        let (secrets, _) = SecretStores::new(&config);
+        let (clusters, _) = reflector::store();
+        let (_, config) = watch::channel(config);
+        let (_, handle) =
+            SyncManager::new(MockService::new().into_client(), config, secrets, clusters);
+        handle
+    }
+
+    #[tokio::test]
+    async fn test_watch_manager_access_records_missing_permissions() {
+        let mock = MockService::new().on_post(REVIEWS, 201, &review(false));
+        let handle = make_handle();
+        let shutdown = CancellationToken::new();
+        shutdown.cancel();
+
+        watch_manager_access(mock.into_client(), &test_config(), handle.clone(), shutdown)
+            .await
+            .unwrap();
+
+        let missing = handle.status().missing_permissions(LOCAL_CLUSTER);
+        assert!(missing.contains(&"list secrets cluster-wide".to_string()));
+        assert!(handle.metrics().encode().contains(&format!(
+            r#"outrider_missing_permissions{{cluster="local"}} {}"#,
+            missing.len()
+        )));
+    }
+}
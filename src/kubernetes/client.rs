@@ -7,7 +7,7 @@ use crate::config::Config;
 use crate::error::{OutriderError, Result};
 use crate::types::cluster::Cluster;
 use k8s_openapi::api::core::v1::Secret;
-use kube::{config::KubeConfigOptions, Api, Client, Config as KConfig, ResourceExt};
+use kube::{Api, Client, Config as KConfig, ResourceExt, config::KubeConfigOptions};
 use tracing::{debug, info, instrument};
 
 /// Create a Kubernetes client for a downstream cluster
@@ -56,9 +56,7 @@ async fn create_testing_client(cluster: &Cluster) -> Result<Client> {
 async fn get_cluster_kubeconfig(client: &Client, cluster: &Cluster) -> Result<String> {
     let cluster_name = cluster.name_any();
     let secret_name = cluster.kubeconfig_secret_name();
-    let namespace = cluster
-        .namespace()
-        .unwrap_or_else(|| "cattle-system".to_string());
+    let namespace = cluster.kubeconfig_secret_namespace();
     let secrets: Api<Secret> = Api::namespaced(client.clone(), &namespace);
 
     info!(
@@ -67,21 +65,21 @@ async fn get_cluster_kubeconfig(client: &Client, cluster: &Cluster) -> Result<St
     );
 
     let secret = secrets.get(&secret_name).await.map_err(|e| {
-        OutriderError::KubeconfigError(format!(
+        OutriderError::KubeconfigUnavailable(format!(
             "Failed to get kubeconfig secret for cluster {}: {}",
             cluster_name, e
         ))
     })?;
 
     let Some(data) = secret.data.as_ref() else {
-        return Err(OutriderError::KubeconfigError(format!(
+        return Err(OutriderError::KubeconfigUnavailable(format!(
             "Kubeconfig secret for cluster {} has no data",
             cluster_name
         )));
     };
 
     let Some(kubeconfig_data) = data.get("value") else {
-        return Err(OutriderError::KubeconfigError(format!(
+        return Err(OutriderError::KubeconfigUnavailable(format!(
             "Kubeconfig secret for cluster {} does not contain 'value' key",
             cluster_name
         )));
@@ -99,8 +97,9 @@ async fn get_cluster_kubeconfig(client: &Client, cluster: &Cluster) -> Result<St
 async fn create_client_from_kubeconfig(kubeconfig: &str) -> Result<Client> {
     use kube::config::Kubeconfig;
 
-    let kubeconfig_parsed: Kubeconfig = serde_yaml::from_str(kubeconfig)
-        .map_err(|e| OutriderError::KubeconfigError(format!("Failed to parse kubeconfig: {}", e)))?;
+    let kubeconfig_parsed: Kubeconfig = serde_yaml::from_str(kubeconfig).map_err(|e| {
+        OutriderError::KubeconfigError(format!("Failed to parse kubeconfig: {}", e))
+    })?;
 
     let client_config =
         kube::Config::from_custom_kubeconfig(kubeconfig_parsed, &KubeConfigOptions::default())
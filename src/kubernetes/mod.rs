@@ -3,10 +3,11 @@
 
 //! Kubernetes utilities for CRD discovery, client creation, and namespace management.
 
+pub mod access;
 pub mod client;
 pub mod crd;
 pub mod namespaces;
 
 pub use client::create_downstream_client;
 pub use crd::wait_for_cluster_crd;
-pub use namespaces::ensure_namespace_exists;
+pub use namespaces::{any_namespace_absent, ensure_namespace_exists};
@@ -6,53 +6,100 @@
 use crate::error::{OutriderError, Result};
 use k8s_openapi::api::core::v1::Namespace;
 use kube::{
-    api::{ObjectMeta, PostParams},
     Api, Client,
+    api::{ObjectMeta, PostParams},
 };
 use tracing::{debug, info, instrument};
 
-/// Ensure a namespace exists in the cluster, create if it doesn't
+/// Ensure a namespace exists in the cluster, create if it doesn't.
+/// In a dry run the creation is only validated by the API server.
+/// Returns whether the namespace was (or would have been) created.
 #[instrument(skip(client))]
-pub async fn ensure_namespace_exists(client: &Client, namespace: &str) -> Result<()> {
+pub async fn ensure_namespace_exists(
+    client: &Client,
+    namespace: &str,
+    dry_run: bool,
+) -> Result<bool> {
     let namespaces: Api<Namespace> = Api::all(client.clone());
 
     match namespaces.get(namespace).await {
         Ok(_) => {
             debug!("Namespace {} already exists", namespace);
-            Ok(())
+            Ok(false)
         }
-        Err(kube::Error::Api(err)) if err.code == 404 => {
-            info!("Creating namespace {}", namespace);
-            let ns = Namespace {
-                metadata: ObjectMeta {
-                    name: Some(namespace.to_string()),
-                    ..Default::default()
-                },
+        Err(kube::Error::Api(err)) if err.code == 404 && dry_run => {
+            info!("Dry run: would create namespace {}", namespace);
+            let pp = PostParams {
+                dry_run: true,
                 ..Default::default()
             };
-            namespaces.create(&PostParams::default(), &ns).await?;
+            namespaces.create(&pp, &new_namespace(namespace)).await?;
+            Ok(true)
+        }
+        Err(kube::Error::Api(err)) if err.code == 404 => {
+            info!("Creating namespace {}", namespace);
+            namespaces
+                .create(&PostParams::default(), &new_namespace(namespace))
+                .await?;
             info!("Namespace {} created successfully", namespace);
-            Ok(())
+            Ok(true)
         }
-        Err(e) => Err(OutriderError::NamespaceError(format!(
-            "Failed to check/create namespace {}: {}",
-            namespace, e
-        ))),
+        Err(e) => Err(OutriderError::NamespaceError {
+            namespace: namespace.to_string(),
+            source: e,
+        }),
+    }
+}
+
+/// Whether any of `namespaces` does not exist yet. A namespace that may not be
+/// looked up counts as existing, as the missing `get` permission is reported
+/// by the permission check instead.
+pub async fn any_namespace_absent(
+    client: &Client,
+    namespaces: impl IntoIterator<Item = &String>,
+) -> Result<bool> {
+    let api: Api<Namespace> = Api::all(client.clone());
+    for namespace in namespaces {
+        match api.get(namespace).await {
+            Ok(_) => {}
+            Err(kube::Error::Api(err)) if err.code == 404 => return Ok(true),
+            Err(kube::Error::Api(err)) if err.code == 403 => {}
+            Err(e) => {
+                return Err(OutriderError::NamespaceError {
+                    namespace: namespace.to_string(),
+                    source: e,
+                });
+            }
+        }
+    }
+    Ok(false)
+}
+
+fn new_namespace(name: &str) -> Namespace {
+    Namespace {
+        metadata: ObjectMeta {
+            name: Some(name.to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::{namespace_json, MockService};
+    use crate::test_utils::{MockService, namespace_json};
 
     #[tokio::test]
     async fn test_namespace_already_exists() {
-        let mock = MockService::new()
-            .on_get("/api/v1/namespaces/test-ns", 200, &namespace_json("test-ns"));
+        let mock = MockService::new().on_get(
+            "/api/v1/namespaces/test-ns",
+            200,
+            &namespace_json("test-ns"),
+        );
 
         let client = mock.into_client();
-        let result = ensure_namespace_exists(&client, "test-ns").await;
+        let result = ensure_namespace_exists(&client, "test-ns", false).await;
 
         assert!(result.is_ok());
     }
@@ -70,11 +117,29 @@ mod tests {
             .on_post("/api/v1/namespaces", 201, &namespace_json("new-ns"));
 
         let client = mock.into_client();
-        let result = ensure_namespace_exists(&client, "new-ns").await;
+        let result = ensure_namespace_exists(&client, "new-ns", false).await;
 
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_namespace_dry_run_create() {
+        let mock = MockService::new()
+            .on_get(
+                "/api/v1/namespaces/new-ns",
+                404,
+                r#"{"kind":"Status","apiVersion":"v1","status":"Failure","reason":"NotFound","code":404}"#,
+            )
+            .on_post("/api/v1/namespaces", 201, &namespace_json("new-ns"));
+
+        let result = ensure_namespace_exists(&mock.clone().into_client(), "new-ns", true).await;
+
+        assert!(result.unwrap());
+        let create = mock.requests().pop().unwrap();
+        assert!(create.starts_with("POST /api/v1/namespaces?"));
+        assert!(create.contains("dryRun=All"));
+    }
+
     #[tokio::test]
     async fn test_namespace_error_propagates() {
         let mock = MockService::new()
@@ -86,8 +151,25 @@ mod tests {
             );
 
         let client = mock.into_client();
-        let result = ensure_namespace_exists(&client, "error-ns").await;
+        let result = ensure_namespace_exists(&client, "error-ns", false).await;
 
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_any_namespace_absent() {
+        let mock = MockService::new()
+            .on_get("/api/v1/namespaces/team-a", 200, &namespace_json("team-a"))
+            .on_get(
+                "/api/v1/namespaces/team-b",
+                404,
+                r#"{"kind":"Status","apiVersion":"v1","status":"Failure","reason":"NotFound","code":404}"#,
+            );
+        let client = mock.into_client();
+        let existing = ["team-a".to_string()];
+        let with_new = ["team-a".to_string(), "team-b".to_string()];
+
+        assert!(!any_namespace_absent(&client, &existing).await.unwrap());
+        assert!(any_namespace_absent(&client, &with_new).await.unwrap());
+    }
 }
@@ -0,0 +1,260 @@
+// Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Lease-based leader election, so only one replica writes to downstream clusters.
+
+use crate::config::LeaderElectionConfig;
+use crate::constants::leader::{LEASE_DURATION_SECS, RENEW_DEADLINE_SECS, RETRY_PERIOD_SECS};
+use crate::health::Health;
+use k8s_openapi::api::coordination::v1::{Lease, LeaseSpec};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::MicroTime;
+use k8s_openapi::chrono::{DateTime, Duration as ChronoDuration, Utc};
+use kube::api::{ObjectMeta, PostParams};
+use kube::{Api, Client};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time::{Instant, sleep};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+const COMPONENT: &str = "leader-election";
+
+/// Competes for a Lease with the other replicas and publishes whether this replica holds it
+pub struct LeaderElector {
+    leases: Api<Lease>,
+    lease_name: String,
+    identity: String,
+}
+
+impl LeaderElector {
+    pub fn new(client: Client, config: &LeaderElectionConfig) -> Self {
+        Self {
+            leases: Api::namespaced(client, &config.namespace),
+            lease_name: config.lease_name.clone(),
+            identity: config.identity.clone(),
+        }
+    }
+
+    /// Keep acquiring or renewing the lease until `stop` is cancelled,
+    /// publishing leadership changes on `leader`
+    pub async fn run(
+        &self,
+        leader: &watch::Sender<bool>,
+        health: Health,
+        stop: CancellationToken,
+    ) -> anyhow::Result<()> {
+        info!(
+            "Competing for lease '{}' as '{}'",
+            self.lease_name, self.identity
+        );
+        let renew_deadline = Duration::from_secs(RENEW_DEADLINE_SECS);
+        let mut last_renewed: Option<Instant> = None;
+
+        loop {
+            let is_leader = match self.try_acquire_or_renew().await {
+                Ok(held) => {
+                    health.succeeded(COMPONENT);
+                    last_renewed = held.then(Instant::now);
+                    held
+                }
+                Err(e) => {
+                    health.failed(COMPONENT);
+                    warn!("Failed to acquire or renew lease: {}", e);
+                    // Keep leading until the renew deadline, well before the lease
+                    // expires and another replica may take over
+                    last_renewed.is_some_and(|at| at.elapsed() < renew_deadline)
+                }
+            };
+
+            leader.send_if_modified(|current| {
+                if *current == is_leader {
+                    return false;
+                }
+                if is_leader {
+                    info!("Acquired lease '{}', now leading", self.lease_name);
+                } else {
+                    warn!("Lost lease '{}', standing by", self.lease_name);
+                }
+                *current = is_leader;
+                true
+            });
+
+            tokio::select! {
+                _ = sleep(Duration::from_secs(RETRY_PERIOD_SECS)) => {}
+                _ = stop.cancelled() => return Ok(()),
+            }
+        }
+    }
+
+    /// Give up the lease if this replica holds it, so a standby can take over
+    /// without waiting for it to expire
+    pub async fn release(&self) {
+        match self.try_release().await {
+            Ok(true) => info!("Released lease '{}'", self.lease_name),
+            Ok(false) => {}
+            Err(e) => warn!("Failed to release lease '{}': {}", self.lease_name, e),
+        }
+    }
+
+    async fn try_release(&self) -> kube::Result<bool> {
+        let Some(mut lease) = self.leases.get_opt(&self.lease_name).await? else {
+            return Ok(false);
+        };
+        let Some(spec) = lease
+            .spec
+            .as_mut()
+            .filter(|spec| spec.holder_identity.as_deref() == Some(self.identity.as_str()))
+        else {
+            return Ok(false);
+        };
+
+        spec.holder_identity = None;
+        self.leases
+            .replace(&self.lease_name, &PostParams::default(), &lease)
+            .await?;
+        Ok(true)
+    }
+
+    /// Returns whether this replica holds the lease after the attempt
+    async fn try_acquire_or_renew(&self) -> kube::Result<bool> {
+        let current = self.leases.get_opt(&self.lease_name).await?;
+        let current_spec = current.as_ref().and_then(|l| l.spec.as_ref());
+        let Some(spec) = desired_spec(current_spec, &self.identity, Utc::now()) else {
+            return Ok(false);
+        };
+
+        let lease = Lease {
+            metadata: ObjectMeta {
+                name: Some(self.lease_name.clone()),
+                // Replacing with the observed resourceVersion fails if another
+                // replica updated the lease in the meantime
+                resource_version: current
+                    .as_ref()
+                    .and_then(|l| l.metadata.resource_version.clone()),
+                ..Default::default()
+            },
+            spec: Some(spec),
+        };
+
+        let result = match current {
+            None => self.leases.create(&PostParams::default(), &lease).await,
+            Some(_) => {
+                self.leases
+                    .replace(&self.lease_name, &PostParams::default(), &lease)
+                    .await
+            }
+        };
+
+        match result {
+            Ok(_) => Ok(true),
+            // Another replica won the race
+            Err(kube::Error::Api(e)) if e.code == 409 => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// The lease to write to hold or take leadership, or None if another
+/// replica holds an unexpired lease
+fn desired_spec(
+    current: Option<&LeaseSpec>,
+    identity: &str,
+    now: DateTime<Utc>,
+) -> Option<LeaseSpec> {
+    let lease_duration_seconds = Some(LEASE_DURATION_SECS as i32);
+
+    match current {
+        Some(spec) if spec.holder_identity.as_deref() == Some(identity) => Some(LeaseSpec {
+            renew_time: Some(MicroTime(now)),
+            lease_duration_seconds,
+            ..spec.clone()
+        }),
+        Some(spec) if !is_expired(spec, now) => None,
+        _ => Some(LeaseSpec {
+            holder_identity: Some(identity.to_string()),
+            acquire_time: Some(MicroTime(now)),
+            renew_time: Some(MicroTime(now)),
+            lease_duration_seconds,
+            lease_transitions: Some(
+                current
+                    .map(|spec| spec.lease_transitions.unwrap_or(0) + 1)
+                    .unwrap_or(0),
+            ),
+        }),
+    }
+}
+
+/// Whether a lease is free to take: released, or not renewed within its duration
+fn is_expired(spec: &LeaseSpec, now: DateTime<Utc>) -> bool {
+    let (Some(_), Some(renew_time)) = (&spec.holder_identity, &spec.renew_time) else {
+        return true;
+    };
+    let duration = spec
+        .lease_duration_seconds
+        .unwrap_or(LEASE_DURATION_SECS as i32);
+    renew_time.0 + ChronoDuration::seconds(duration.into()) < now
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn held_by(identity: &str, renewed: DateTime<Utc>) -> LeaseSpec {
+        LeaseSpec {
+            holder_identity: Some(identity.to_string()),
+            acquire_time: Some(MicroTime(renewed)),
+            renew_time: Some(MicroTime(renewed)),
+            lease_duration_seconds: Some(LEASE_DURATION_SECS as i32),
+            lease_transitions: Some(3),
+        }
+    }
+
+    #[test]
+    fn test_acquires_missing_lease() {
+        let now = Utc::now();
+        let spec = desired_spec(None, "pod-a", now).unwrap();
+
+        assert_eq!(spec.holder_identity.as_deref(), Some("pod-a"));
+        assert_eq!(spec.renew_time, Some(MicroTime(now)));
+        assert_eq!(spec.lease_transitions, Some(0));
+    }
+
+    #[test]
+    fn test_renews_own_lease() {
+        let acquired = Utc::now();
+        let now = acquired + ChronoDuration::seconds(5);
+        let spec = desired_spec(Some(&held_by("pod-a", acquired)), "pod-a", now).unwrap();
+
+        assert_eq!(spec.acquire_time, Some(MicroTime(acquired)));
+        assert_eq!(spec.renew_time, Some(MicroTime(now)));
+        assert_eq!(spec.lease_transitions, Some(3));
+    }
+
+    #[test]
+    fn test_respects_lease_held_by_other() {
+        let renewed = Utc::now();
+        let now = renewed + ChronoDuration::seconds(LEASE_DURATION_SECS as i64 - 1);
+
+        assert!(desired_spec(Some(&held_by("pod-b", renewed)), "pod-a", now).is_none());
+    }
+
+    #[test]
+    fn test_takes_over_expired_lease() {
+        let renewed = Utc::now();
+        let now = renewed + ChronoDuration::seconds(LEASE_DURATION_SECS as i64 + 1);
+        let spec = desired_spec(Some(&held_by("pod-b", renewed)), "pod-a", now).unwrap();
+
+        assert_eq!(spec.holder_identity.as_deref(), Some("pod-a"));
+        assert_eq!(spec.acquire_time, Some(MicroTime(now)));
+        assert_eq!(spec.lease_transitions, Some(4));
+    }
+
+    #[test]
+    fn test_takes_over_released_lease() {
+        let mut released = held_by("pod-b", Utc::now());
+        released.holder_identity = None;
+
+        let spec = desired_spec(Some(&released), "pod-a", Utc::now()).unwrap();
+        assert_eq!(spec.holder_identity.as_deref(), Some("pod-a"));
+    }
+}
@@ -1,13 +1,22 @@
 // Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod admin;
 pub mod config;
 pub mod constants;
+pub mod diff;
 pub mod error;
+pub mod health;
 pub mod kubernetes;
+pub mod leader;
+pub mod metrics;
+pub mod plan;
 pub mod reconcilers;
+pub mod reload;
+pub mod server;
 pub mod sync;
+pub mod telemetry;
 pub mod types;
 
 #[cfg(test)]
-pub mod test_utils;
\ No newline at end of file
+pub mod test_utils;
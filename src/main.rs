@@ -1,53 +1,238 @@
 // Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
 // SPDX-License-Identifier: Apache-2.0
 use anyhow::Result;
+use clap::{Parser, Subcommand};
 use kube::Client;
-use tracing::{info, warn};
+use kube::runtime::reflector;
+use tokio::signal::unix::{Signal, SignalKind, signal};
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
 
-use outrider::config::Config;
+use outrider::admin::AdminState;
+use outrider::config::{Config, config_file};
+use outrider::diff;
+use outrider::kubernetes::access::watch_manager_access;
 use outrider::kubernetes::wait_for_cluster_crd;
+use outrider::leader::LeaderElector;
+use outrider::plan;
 use outrider::reconcilers::{ClusterReconciler, SecretReconciler};
-use outrider::sync::SyncManager;
+use outrider::reload::ConfigReloader;
+use outrider::server;
+use outrider::sync::{SecretStores, SyncManager};
+use outrider::telemetry;
+
+/// Copies annotated secrets from the Rancher manager cluster to downstream clusters
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the operator (the default)
+    Run,
+    /// Preview where enabled secrets would be copied, without writing anything
+    ///
+    /// Lists every enabled secret with each ready cluster and target namespace
+    /// it would be copied to, and whether the downstream secret would be
+    /// created, updated or left unchanged.
+    Plan,
+    /// Compare enabled secrets with their copies in the downstream clusters
+    ///
+    /// Reports, for each ready cluster, secrets that are missing, stale (their
+    /// content hash does not match the source), extra (managed by Outrider but
+    /// no longer sourced) or conflicting (not managed by Outrider). Only secret
+    /// names and data keys are shown, never values.
+    Diff,
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize tracing
-    tracing_subscriber::fmt::init();
+    let cli = Cli::parse();
+    // Load configuration, which also selects the log format
+    let config = Config::load()?;
 
-    info!("Starting Outrider operator");
+    match cli.command.unwrap_or(Command::Run) {
+        Command::Run => run(config).await,
+        Command::Plan => print_plan(config).await,
+        Command::Diff => print_diff(config).await,
+    }
+}
+
+/// Print where every enabled secret would be copied to
+async fn print_plan(config: Config) -> Result<()> {
+    telemetry::init_cli(&config)?;
+    let client = Client::try_default().await?;
+
+    let planned = plan::plan(&client, &config).await?;
+    print!("{}", plan::render(&planned));
+    Ok(())
+}
+
+/// Print the downstream secrets that are not in sync
+async fn print_diff(config: Config) -> Result<()> {
+    telemetry::init_cli(&config)?;
+    let client = Client::try_default().await?;
+
+    let differences = diff::diff(&client, &config).await?;
+    print!("{}", diff::render(&differences));
+    Ok(())
+}
 
-    // Load configuration
-    let config = Config::from_env()?;
+/// Run the operator until SIGTERM
+async fn run(config: Config) -> Result<()> {
+    let telemetry = telemetry::init(&config)?;
+
+    info!("Starting Outrider operator");
     info!(
         "Configuration loaded: default_target_namespace={}",
         config.default_target_namespace
     );
+    if config.dry_run {
+        info!("Dry run enabled: downstream secrets and namespaces are not modified");
+    }
+    if !config.watch_namespaces.is_empty() {
+        info!(
+            "Watching secrets in namespaces: {}",
+            config.watch_namespaces.join(", ")
+        );
+    }
 
     // Create Kubernetes client
     let client = Client::try_default().await?;
     info!("Connected to Kubernetes cluster");
 
-    // Wait for Rancher Cluster CRD before starting reconcilers
-    info!("Waiting for Rancher Cluster CRD to become available...");
-    wait_for_cluster_crd(&client).await?;
+    // Caches populated by the reconcilers and read by the sync manager
+    let (secret_stores, secret_writers) = SecretStores::new(&config);
+    let (cluster_store, cluster_writer) = reflector::store();
+    let admin_stores = (secret_stores.clone(), cluster_store.clone());
+
+    // Settings that apply without a restart are picked up by the sync manager on reload
+    let (config_tx, config_rx) = watch::channel(config.clone());
 
-    // Create the sync manager and get a handle for reconcilers
-    let (sync_manager, sync_handle) = SyncManager::new(client.clone(), config.clone());
+    // Create the sync manager and get a handle for reconcilers. The manager
+    // buffers events until the caches are populated.
+    let (sync_manager, sync_handle) =
+        SyncManager::new(client.clone(), config_rx, secret_stores, cluster_store);
 
     // Create reconcilers with the sync handle
-    let secret_reconciler = SecretReconciler::new(client.clone(), sync_handle.clone());
-    let cluster_reconciler = ClusterReconciler::new(client.clone(), sync_handle);
+    let secret_reconciler =
+        SecretReconciler::new(client.clone(), config.clone(), sync_handle.clone());
+    let cluster_reconciler = ClusterReconciler::new(client.clone(), sync_handle.clone());
+
+    // Cancelled on SIGTERM or Ctrl-C, stopping the controllers and draining the sync manager
+    let shutdown = CancellationToken::new();
+    let terminate = signal(SignalKind::terminate())?;
+    tokio::spawn(cancel_on_signal(terminate, shutdown.clone()));
+    // Cancelled once the sync manager has stopped writing downstream
+    let stopped = CancellationToken::new();
+
+    // Wait for Rancher Cluster CRD before starting reconcilers. The HTTP server
+    // already runs meanwhile, reporting not ready.
+    let health = sync_handle.health().clone();
+    let reconcilers = async {
+        info!("Waiting for Rancher Cluster CRD to become available...");
+        tokio::select! {
+            crd = wait_for_cluster_crd(&client) => crd?,
+            _ = shutdown.cancelled() => return anyhow::Ok(()),
+        }
+        health.set_crd_ready();
 
-    info!("Starting reconcilers...");
+        info!("Starting reconcilers...");
+        tokio::try_join!(
+            secret_reconciler.run(secret_writers, shutdown.clone()),
+            cluster_reconciler.run(cluster_writer, shutdown.clone())
+        )?;
+        anyhow::Ok(())
+    };
 
-    // Run sync manager and both reconcilers concurrently
+    // Only the leader runs the sync manager; standby replicas keep warm caches.
+    // Without leader election this replica always leads.
+    let (leader_tx, leader_rx) = watch::channel(config.leader_election.is_none());
+    let elector = config
+        .leader_election
+        .as_ref()
+        .map(|lease| LeaderElector::new(client.clone(), lease));
+    let admin = config.admin_token.as_deref().map(|token| {
+        let (secrets, clusters) = admin_stores;
+        AdminState::new(
+            token,
+            sync_handle.clone(),
+            client.clone(),
+            secrets,
+            clusters,
+            leader_rx.clone(),
+        )
+    });
+    let leader_election = async {
+        match &elector {
+            // Keep the lease until the sync manager has drained
+            Some(elector) => {
+                elector
+                    .run(&leader_tx, sync_handle.health().clone(), stopped.clone())
+                    .await
+            }
+            None => Ok(()),
+        }
+    };
+
+    let reload = async {
+        match config_file() {
+            Some(path) => {
+                ConfigReloader::new(path, config_tx)
+                    .run(stopped.clone())
+                    .await
+            }
+            None => Ok(()),
+        }
+    };
+
+    let sync = async {
+        let result = sync_manager.run(leader_rx, shutdown.clone()).await;
+        stopped.cancel();
+        result
+    };
+
+    // Run the HTTP server, leader election, config reloading, the manager
+    // cluster permission check, sync manager and both reconcilers concurrently
     tokio::try_join!(
-        sync_manager.run(),
-        secret_reconciler.run(),
-        cluster_reconciler.run()
+        server::serve(
+            config.http_port,
+            sync_handle.clone(),
+            admin,
+            stopped.clone()
+        ),
+        leader_election,
+        reload,
+        watch_manager_access(
+            client.clone(),
+            &config,
+            sync_handle.clone(),
+            shutdown.clone()
+        ),
+        sync,
+        reconcilers
     )?;
 
-    // This should never be reached as reconcilers run forever
-    warn!("All reconcilers stopped unexpectedly");
+    // Let a standby take over right away instead of waiting for the lease to expire
+    if let Some(elector) = &elector {
+        elector.release().await;
+    }
+
+    info!("Shutdown complete");
+    telemetry.shutdown().await;
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Cancel `shutdown` on SIGTERM or Ctrl-C
+async fn cancel_on_signal(mut terminate: Signal, shutdown: CancellationToken) {
+    tokio::select! {
+        _ = terminate.recv() => {}
+        _ = tokio::signal::ctrl_c() => {}
+    }
+    info!("Received shutdown signal, stopping...");
+    shutdown.cancel();
+}
@@ -0,0 +1,353 @@
+// Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Prometheus metrics exposed on the `/metrics` endpoint.
+
+use crate::error::OutriderError;
+use crate::sync::CopyOutcome;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+};
+use std::time::Duration;
+
+/// Buckets in seconds for the time from a source change to the downstream apply.
+/// The lower end covers the debounce window, the upper end retries with backoff.
+const LATENCY_BUCKETS: &[f64] = &[
+    0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0,
+];
+
+/// Operator metrics, registered in their own registry.
+/// Cheaply cloneable; clones share the same series.
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Registry,
+    syncs: IntCounterVec,
+    sync_failures: IntCounterVec,
+    sync_skips: IntCounterVec,
+    sync_latency: HistogramVec,
+    clusters: IntGaugeVec,
+    queue_depth: IntGauge,
+    coalesced_events: IntCounter,
+    pending_secrets: IntGaugeVec,
+    paused_clusters: IntGaugeVec,
+    kubeconfig_errors: IntCounterVec,
+    missing_permissions: IntGaugeVec,
+    state_save_failures: IntCounter,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let syncs = IntCounterVec::new(
+            Opts::new(
+                "outrider_syncs_total",
+                "Secret copies that created or updated a downstream secret",
+            ),
+            &["cluster", "secret"],
+        )
+        .unwrap();
+        let sync_failures = IntCounterVec::new(
+            Opts::new("outrider_sync_failures_total", "Failed secret copies"),
+            &["cluster", "secret"],
+        )
+        .unwrap();
+        let sync_skips = IntCounterVec::new(
+            Opts::new(
+                "outrider_sync_skips_total",
+                "Secret copies skipped because the downstream secret was up to date",
+            ),
+            &["cluster", "secret"],
+        )
+        .unwrap();
+        let sync_latency = HistogramVec::new(
+            HistogramOpts::new(
+                "outrider_sync_latency_seconds",
+                "Time from a source secret change to the downstream apply",
+            )
+            .buckets(LATENCY_BUCKETS.to_vec()),
+            &["cluster"],
+        )
+        .unwrap();
+        let clusters = IntGaugeVec::new(
+            Opts::new("outrider_clusters", "Downstream clusters by state"),
+            &["state"],
+        )
+        .unwrap();
+        let queue_depth = IntGauge::new(
+            "outrider_event_queue_depth",
+            "Events waiting in the SyncManager channel",
+        )
+        .unwrap();
+        let coalesced_events = IntCounter::new(
+            "outrider_coalesced_events_total",
+            "Events superseded by a newer event for the same secret or cluster",
+        )
+        .unwrap();
+        let pending_secrets = IntGaugeVec::new(
+            Opts::new(
+                "outrider_pending_secrets",
+                "Secrets queued for a cluster, including scheduled retries",
+            ),
+            &["cluster"],
+        )
+        .unwrap();
+        let paused_clusters = IntGaugeVec::new(
+            Opts::new(
+                "outrider_cluster_paused",
+                "Whether writing to a cluster is paused, globally or through its annotation",
+            ),
+            &["cluster"],
+        )
+        .unwrap();
+        let kubeconfig_errors = IntCounterVec::new(
+            Opts::new(
+                "outrider_kubeconfig_fetch_errors_total",
+                "Failures to fetch the kubeconfig secret of a cluster",
+            ),
+            &["cluster"],
+        )
+        .unwrap();
+
+        let missing_permissions = IntGaugeVec::new(
+            Opts::new(
+                "outrider_missing_permissions",
+                "Permissions the last access check found missing, per cluster. The manager cluster is \"local\"",
+            ),
+            &["cluster"],
+        )
+        .unwrap();
+
+        let state_save_failures = IntCounter::new(
+            "outrider_state_save_failures_total",
+            "Failed attempts to save the persisted sync state",
+        )
+        .unwrap();
+
+        let registry = Registry::new();
+        registry.register(Box::new(syncs.clone())).unwrap();
+        registry.register(Box::new(sync_failures.clone())).unwrap();
+        registry.register(Box::new(sync_skips.clone())).unwrap();
+        registry.register(Box::new(sync_latency.clone())).unwrap();
+        registry.register(Box::new(clusters.clone())).unwrap();
+        registry.register(Box::new(queue_depth.clone())).unwrap();
+        registry
+            .register(Box::new(coalesced_events.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(pending_secrets.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(paused_clusters.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(kubeconfig_errors.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(missing_permissions.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(state_save_failures.clone()))
+            .unwrap();
+
+        Self {
+            registry,
+            syncs,
+            sync_failures,
+            sync_skips,
+            sync_latency,
+            clusters,
+            queue_depth,
+            coalesced_events,
+            pending_secrets,
+            paused_clusters,
+            kubeconfig_errors,
+            missing_permissions,
+            state_save_failures,
+        }
+    }
+
+    /// Record a successful copy of a secret (`namespace/name`) to a cluster.
+    /// `latency` is the time since the source change that caused it.
+    pub fn record_sync(
+        &self,
+        cluster: &str,
+        secret: &str,
+        outcome: &CopyOutcome,
+        latency: Duration,
+    ) {
+        match outcome {
+            // A dry run is counted like the write it stands in for
+            CopyOutcome::Applied { .. } | CopyOutcome::DryRun => {
+                self.syncs.with_label_values(&[cluster, secret]).inc();
+                self.sync_latency
+                    .with_label_values(&[cluster])
+                    .observe(latency.as_secs_f64());
+            }
+            CopyOutcome::Unchanged { .. } => {
+                self.sync_skips.with_label_values(&[cluster, secret]).inc();
+            }
+        }
+    }
+
+    /// Record a failed copy of a secret to a cluster
+    pub fn record_failure(&self, cluster: &str, secret: &str, error: &OutriderError) {
+        self.sync_failures
+            .with_label_values(&[cluster, secret])
+            .inc();
+        if matches!(error, OutriderError::KubeconfigUnavailable(_)) {
+            self.kubeconfig_errors.with_label_values(&[cluster]).inc();
+        }
+    }
+
+    /// Set the number of ready clusters and of clusters that received their initial sync
+    pub fn set_clusters(&self, ready: usize, synced: usize) {
+        self.clusters
+            .with_label_values(&["ready"])
+            .set(ready as i64);
+        self.clusters
+            .with_label_values(&["synced"])
+            .set(synced as i64);
+    }
+
+    /// Set the number of events waiting in the SyncManager channel
+    pub fn set_queue_depth(&self, depth: usize) {
+        self.queue_depth.set(depth as i64);
+    }
+
+    /// Count an event that was superseded by a newer event for the same secret
+    /// or cluster, and therefore did not cause a sync of its own
+    pub fn record_coalesced_event(&self) {
+        self.coalesced_events.inc();
+    }
+
+    /// Count a failed attempt to save the persisted sync state
+    pub fn record_state_save_failure(&self) {
+        self.state_save_failures.inc();
+    }
+
+    /// Set the number of secrets queued for a cluster and whether writing to it is paused
+    pub fn set_cluster_backlog(&self, cluster: &str, pending: usize, paused: bool) {
+        self.pending_secrets
+            .with_label_values(&[cluster])
+            .set(pending as i64);
+        self.paused_clusters
+            .with_label_values(&[cluster])
+            .set(paused as i64);
+    }
+
+    /// Drop the backlog series of a cluster whose worker was stopped.
+    /// Without `cluster`, drop those of all clusters.
+    pub fn forget_cluster_backlog(&self, cluster: Option<&str>) {
+        match cluster {
+            Some(cluster) => {
+                let _ = self.pending_secrets.remove_label_values(&[cluster]);
+                let _ = self.paused_clusters.remove_label_values(&[cluster]);
+            }
+            None => {
+                self.pending_secrets.reset();
+                self.paused_clusters.reset();
+            }
+        }
+    }
+
+    /// Set the number of permissions the last access check of a cluster found missing
+    pub fn set_missing_permissions(&self, cluster: &str, missing: usize) {
+        self.missing_permissions
+            .with_label_values(&[cluster])
+            .set(missing as i64);
+    }
+
+    /// Drop the missing permissions series of a cluster that is no longer ready
+    pub fn forget_missing_permissions(&self, cluster: &str) {
+        let _ = self.missing_permissions.remove_label_values(&[cluster]);
+    }
+
+    /// Render all metrics in the Prometheus text format
+    pub fn encode(&self) -> String {
+        let mut buffer = Vec::new();
+        // Encoding into a Vec only fails for malformed metric families
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_sync_counts_applied_and_skipped() {
+        let metrics = Metrics::new();
+        let applied = CopyOutcome::Applied {
+            hash: "abc".to_string(),
+        };
+        let unchanged = CopyOutcome::Unchanged {
+            hash: "abc".to_string(),
+        };
+
+        metrics.record_sync(
+            "cluster-a",
+            "default/creds",
+            &applied,
+            Duration::from_secs(1),
+        );
+        metrics.record_sync("cluster-a", "default/creds", &unchanged, Duration::ZERO);
+
+        let output = metrics.encode();
+        assert!(
+            output
+                .contains(r#"outrider_syncs_total{cluster="cluster-a",secret="default/creds"} 1"#)
+        );
+        assert!(output.contains(
+            r#"outrider_sync_skips_total{cluster="cluster-a",secret="default/creds"} 1"#
+        ));
+        assert!(output.contains(r#"outrider_sync_latency_seconds_count{cluster="cluster-a"} 1"#));
+    }
+
+    #[test]
+    fn test_record_failure_counts_kubeconfig_errors() {
+        let metrics = Metrics::new();
+
+        metrics.record_failure(
+            "cluster-a",
+            "default/creds",
+            &OutriderError::KubeconfigUnavailable("missing".to_string()),
+        );
+        metrics.record_failure(
+            "cluster-a",
+            "default/creds",
+            &OutriderError::SyncTimeout("slow".to_string()),
+        );
+
+        let output = metrics.encode();
+        assert!(output.contains(
+            r#"outrider_sync_failures_total{cluster="cluster-a",secret="default/creds"} 2"#
+        ));
+        assert!(
+            output.contains(r#"outrider_kubeconfig_fetch_errors_total{cluster="cluster-a"} 1"#)
+        );
+    }
+
+    #[test]
+    fn test_gauges() {
+        let metrics = Metrics::new();
+
+        metrics.set_clusters(3, 2);
+        metrics.set_queue_depth(5);
+        metrics.set_missing_permissions("local", 2);
+
+        let output = metrics.encode();
+        assert!(output.contains(r#"outrider_clusters{state="ready"} 3"#));
+        assert!(output.contains(r#"outrider_clusters{state="synced"} 2"#));
+        assert!(output.contains("outrider_event_queue_depth 5"));
+        assert!(output.contains(r#"outrider_missing_permissions{cluster="local"} 2"#));
+    }
+}
@@ -0,0 +1,286 @@
+// Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Preview of where enabled secrets would be copied, without writing anything.
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::kubernetes::create_downstream_client;
+use crate::sync::secrets::{
+    create_downstream_secret, get_target_namespace, recorded_content_hash, secret_key,
+};
+use crate::sync::{get_enabled_secrets, get_ready_clusters};
+use crate::types::cluster::Cluster;
+use futures::{StreamExt, stream};
+use k8s_openapi::api::core::v1::Secret;
+use kube::{Api, Client, ResourceExt};
+use std::fmt;
+use tokio::time::timeout;
+
+/// What syncing would do to a downstream secret
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Create,
+    Update,
+    Unchanged,
+    /// The downstream cluster or secret could not be read
+    Error(String),
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Create => write!(f, "create"),
+            Action::Update => write!(f, "update"),
+            Action::Unchanged => write!(f, "unchanged"),
+            Action::Error(e) => write!(f, "error: {}", e),
+        }
+    }
+}
+
+/// A source secret and where it would be copied to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedCopy {
+    /// Source secret as `namespace/name`
+    pub secret: String,
+    pub cluster: String,
+    /// Target namespace in the downstream cluster
+    pub namespace: String,
+    pub action: Action,
+}
+
+/// Plan the copy of every enabled secret to every ready cluster, reading the
+/// downstream secrets to tell what would change
+pub async fn plan(client: &Client, config: &Config) -> Result<Vec<PlannedCopy>> {
+    let secrets = get_enabled_secrets(client, config).await?;
+    let clusters = get_ready_clusters(client).await?;
+
+    let mut planned = plan_clusters(client, config, &clusters, &secrets).await;
+    planned.sort_by(|a, b| (&a.secret, &a.cluster).cmp(&(&b.secret, &b.cluster)));
+    Ok(planned)
+}
+
+/// Plan the clusters concurrently, at most `max_concurrent_syncs` at a time.
+/// A cluster that does not answer within the sync timeout gets an error for
+/// each secret instead.
+async fn plan_clusters(
+    client: &Client,
+    config: &Config,
+    clusters: &[Cluster],
+    secrets: &[Secret],
+) -> Vec<PlannedCopy> {
+    let per_cluster: Vec<Vec<PlannedCopy>> = stream::iter(clusters)
+        .map(|cluster| async move {
+            match timeout(
+                config.sync_timeout,
+                plan_cluster(client, config, cluster, secrets),
+            )
+            .await
+            {
+                Ok(planned) => planned,
+                Err(_) => secrets
+                    .iter()
+                    .map(|secret| PlannedCopy {
+                        secret: secret_key(secret),
+                        cluster: cluster.name_any(),
+                        namespace: get_target_namespace(secret, config).to_string(),
+                        action: Action::Error(format!(
+                            "no response within {:?}",
+                            config.sync_timeout
+                        )),
+                    })
+                    .collect(),
+            }
+        })
+        .buffer_unordered(config.max_concurrent_syncs)
+        .collect()
+        .await;
+
+    per_cluster.into_iter().flatten().collect()
+}
+
+async fn plan_cluster(
+    client: &Client,
+    config: &Config,
+    cluster: &Cluster,
+    secrets: &[Secret],
+) -> Vec<PlannedCopy> {
+    let downstream = create_downstream_client(client, cluster, config).await;
+
+    let mut planned = Vec::with_capacity(secrets.len());
+    for secret in secrets {
+        let desired = create_downstream_secret(secret, get_target_namespace(secret, config));
+        let namespace = desired.namespace().unwrap_or_default();
+        let action = match &downstream {
+            Ok(downstream) => {
+                let existing = Api::<Secret>::namespaced(downstream.clone(), &namespace)
+                    .get_opt(&desired.name_any())
+                    .await;
+                match existing {
+                    Ok(existing) => action(existing.as_ref(), &desired),
+                    Err(e) => Action::Error(e.to_string()),
+                }
+            }
+            Err(e) => Action::Error(e.to_string()),
+        };
+
+        planned.push(PlannedCopy {
+            secret: secret_key(secret),
+            cluster: cluster.name_any(),
+            namespace,
+            action,
+        });
+    }
+    planned
+}
+
+/// Compare the existing downstream secret with the one that would be applied,
+/// the same way a sync does
+fn action(existing: Option<&Secret>, desired: &Secret) -> Action {
+    match existing {
+        None => Action::Create,
+        Some(existing) if recorded_content_hash(existing) == recorded_content_hash(desired) => {
+            Action::Unchanged
+        }
+        Some(_) => Action::Update,
+    }
+}
+
+/// Render a plan as a table, followed by a summary
+pub fn render(planned: &[PlannedCopy]) -> String {
+    let rows: Vec<Vec<String>> = planned
+        .iter()
+        .map(|p| {
+            vec![
+                p.secret.clone(),
+                p.cluster.clone(),
+                p.namespace.clone(),
+                p.action.to_string(),
+            ]
+        })
+        .collect();
+    let mut out = table(&["SECRET", "CLUSTER", "NAMESPACE", "ACTION"], &rows);
+
+    let count = |wanted: fn(&Action) -> bool| planned.iter().filter(|p| wanted(&p.action)).count();
+    out.push_str(&format!(
+        "\n{} to create, {} to update, {} unchanged, {} failed\n",
+        count(|a| *a == Action::Create),
+        count(|a| *a == Action::Update),
+        count(|a| *a == Action::Unchanged),
+        count(|a| matches!(a, Action::Error(_))),
+    ));
+    out
+}
+
+/// Align rows into columns under the header. The last column is not padded.
+pub(crate) fn table(header: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut out = String::new();
+    let header: Vec<String> = header.iter().map(|h| h.to_string()).collect();
+    for row in std::iter::once(&header).chain(rows) {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        out.push_str(line.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::annotations;
+    use crate::test_utils::{MockService, make_cluster, make_secret, test_config};
+    use kube::api::ObjectMeta;
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    fn with_hash(hash: &str) -> Secret {
+        Secret {
+            metadata: ObjectMeta {
+                name: Some("creds".to_string()),
+                annotations: Some(BTreeMap::from([(
+                    annotations::CONTENT_HASH.to_string(),
+                    hash.to_string(),
+                )])),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_action() {
+        let desired = with_hash("abc");
+
+        assert_eq!(action(None, &desired), Action::Create);
+        assert_eq!(action(Some(&with_hash("abc")), &desired), Action::Unchanged);
+        assert_eq!(action(Some(&with_hash("old")), &desired), Action::Update);
+        // Secrets not written by Outrider are taken over
+        assert_eq!(action(Some(&Secret::default()), &desired), Action::Update);
+    }
+
+    #[tokio::test]
+    async fn test_unresponsive_cluster_is_reported_as_error() {
+        let mock = MockService::new()
+            .hang_on_get("/api/v1/namespaces/fleet-default/secrets/unreachable-kubeconfig");
+        let config = Config {
+            sync_timeout: Duration::from_millis(50),
+            ..test_config()
+        };
+        let clusters = [make_cluster("unreachable", true)];
+        let secrets = [make_secret(
+            "fleet-default",
+            "creds",
+            &[("password", "s3cr3t")],
+        )];
+
+        let planned = plan_clusters(&mock.into_client(), &config, &clusters, &secrets).await;
+
+        assert_eq!(
+            planned,
+            vec![PlannedCopy {
+                secret: "fleet-default/creds".to_string(),
+                cluster: "unreachable".to_string(),
+                namespace: "default".to_string(),
+                action: Action::Error("no response within 50ms".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let planned = vec![
+            PlannedCopy {
+                secret: "fleet-default/creds".to_string(),
+                cluster: "downstream-a".to_string(),
+                namespace: "default".to_string(),
+                action: Action::Create,
+            },
+            PlannedCopy {
+                secret: "fleet-default/creds".to_string(),
+                cluster: "b".to_string(),
+                namespace: "custom".to_string(),
+                action: Action::Error("unreachable".to_string()),
+            },
+        ];
+
+        assert_eq!(
+            render(&planned),
+            "SECRET               CLUSTER       NAMESPACE  ACTION\n\
+             fleet-default/creds  downstream-a  default    create\n\
+             fleet-default/creds  b             custom     error: unreachable\n\
+             \n\
+             1 to create, 0 to update, 0 unchanged, 1 failed\n"
+        );
+    }
+}
@@ -4,32 +4,76 @@
 //! Cluster reconciler - watches Rancher Cluster resources and notifies sync manager.
 
 use crate::error::{OutriderError, Result};
+use crate::reconcilers::resync::ResyncRequests;
 use crate::sync::{SyncEvent, SyncManagerHandle};
 use crate::types::cluster::Cluster;
 use futures::StreamExt;
 use kube::{
-    runtime::{controller::Action, watcher, Controller},
     Api, Client, ResourceExt,
+    runtime::{
+        Controller, WatchStreamExt,
+        controller::Action,
+        reflector::{Store, store::Writer},
+        watcher,
+    },
 };
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
-use tracing::{debug, error, warn};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
 
 pub struct ClusterReconciler {
     client: Client,
     sync_handle: SyncManagerHandle,
+    resync_requests: ResyncRequests,
 }
 
 impl ClusterReconciler {
     pub fn new(client: Client, sync_handle: SyncManagerHandle) -> Self {
-        Self { client, sync_handle }
+        Self {
+            client,
+            sync_handle,
+            resync_requests: ResyncRequests::new(),
+        }
     }
 
-    pub async fn run(self) -> anyhow::Result<()> {
+    /// Run the reconciler, keeping the store behind `writer` populated with all clusters.
+    /// Stops once `shutdown` is cancelled and running reconciliations have finished.
+    pub async fn run(
+        self,
+        writer: Writer<Cluster>,
+        shutdown: CancellationToken,
+    ) -> anyhow::Result<()> {
         let clusters: Api<Cluster> = Api::all(self.client.clone());
+        let reader = writer.as_reader();
+        let health = self.sync_handle.health().clone();
         let context = Arc::new(self);
+        let ctx = context.clone();
+        let cache = reader.clone();
+        let stream = watcher(clusters, watcher::Config::default())
+            .default_backoff()
+            .inspect(move |event| match event {
+                Ok(_) => health.succeeded("cluster-watch"),
+                Err(_) => health.failed("cluster-watch"),
+            })
+            .reflect(writer)
+            // Deleted clusters never reach the reconciler, so handle them here
+            .then(move |event| {
+                let ctx = ctx.clone();
+                let cache = cache.clone();
+                async move {
+                    if let Ok(event) = &event {
+                        ctx.handle_deleted(event, &cache).await;
+                    }
+                    event
+                }
+            })
+            .touched_objects();
+        let health = context.sync_handle.health().clone();
 
-        Controller::new(clusters, watcher::Config::default())
+        Controller::for_stream(stream, reader)
+            .graceful_shutdown_on(shutdown.clone().cancelled_owned())
             .run(reconcile, error_policy, context)
             .for_each(|res| async move {
                 match res {
@@ -39,8 +83,40 @@ impl ClusterReconciler {
             })
             .await;
 
+        if !shutdown.is_cancelled() {
+            health.exited("cluster-controller");
+        }
         Ok(())
     }
+
+    /// Stop syncing to clusters that were deleted, or that are missing from the
+    /// cache after the watch was restarted, as if they were no longer ready
+    pub(crate) async fn handle_deleted(
+        &self,
+        event: &watcher::Event<Cluster>,
+        cache: &Store<Cluster>,
+    ) {
+        let deleted = match event {
+            watcher::Event::Delete(cluster) if !cluster.is_local() => vec![cluster.name_any()],
+            watcher::Event::InitDone => {
+                let names: HashSet<String> = cache
+                    .state()
+                    .iter()
+                    .map(|cluster| cluster.name_any())
+                    .collect();
+                self.resync_requests.retain(|name| names.contains(name))
+            }
+            _ => Vec::new(),
+        };
+
+        for name in deleted {
+            info!("Cluster {} was deleted", name);
+            self.resync_requests.forget(&name);
+            self.sync_handle
+                .send(SyncEvent::ClusterBecameNotReady { name })
+                .await;
+        }
+    }
 }
 
 async fn reconcile(cluster: Arc<Cluster>, ctx: Arc<ClusterReconciler>) -> Result<Action> {
@@ -52,9 +128,21 @@ async fn reconcile(cluster: Arc<Cluster>, ctx: Arc<ClusterReconciler>) -> Result
     }
 
     debug!("Reconciling cluster: {}", name);
+    let resync_requested = ctx.resync_requests.requested(&name, &cluster.metadata);
 
-    // Notify the sync manager about the cluster state
-    if cluster.is_ready() {
+    // Notify the sync manager about the cluster state. A cluster that is not
+    // ready gets a full sync once it is ready again anyway.
+    if cluster.is_ready() && resync_requested {
+        info!(
+            "Resync of cluster {} requested through its annotation",
+            name
+        );
+        ctx.sync_handle
+            .send(SyncEvent::ResyncCluster {
+                cluster: (*cluster).clone(),
+            })
+            .await;
+    } else if cluster.is_ready() {
         ctx.sync_handle
             .send(SyncEvent::ClusterBecameReady {
                 cluster: (*cluster).clone(),
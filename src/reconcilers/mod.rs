@@ -4,6 +4,7 @@
 //! Kubernetes reconcilers that react to watch events.
 
 pub mod cluster;
+pub mod resync;
 pub mod secret;
 
 pub use cluster::ClusterReconciler;
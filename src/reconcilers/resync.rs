@@ -0,0 +1,110 @@
+// Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Detection of resync requests made through the resync-requested-at annotation.
+
+use crate::constants::annotations;
+use kube::api::ObjectMeta;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Last seen value of the resync-requested-at annotation for each object
+#[derive(Default)]
+pub struct ResyncRequests {
+    seen: Mutex<HashMap<String, Option<String>>>,
+}
+
+impl ResyncRequests {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the annotation of an object, identified by `key`, and return
+    /// whether it changed since the object was last seen. The first time an
+    /// object is seen is not a request, as the initial sync already covers it.
+    pub fn requested(&self, key: &str, metadata: &ObjectMeta) -> bool {
+        let value = metadata
+            .annotations
+            .as_ref()
+            .and_then(|a| a.get(annotations::RESYNC_REQUESTED_AT))
+            .cloned();
+
+        match self
+            .seen
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), value.clone())
+        {
+            Some(previous) => previous != value && value.is_some(),
+            None => false,
+        }
+    }
+
+    /// Stop tracking an object that was deleted or no longer needs syncing
+    pub fn forget(&self, key: &str) {
+        self.seen.lock().unwrap().remove(key);
+    }
+
+    /// Stop tracking every object for which `exists` returns false, such as
+    /// objects deleted while the watch was interrupted. Returns their keys.
+    pub fn retain(&self, exists: impl Fn(&str) -> bool) -> Vec<String> {
+        let mut removed = Vec::new();
+        self.seen.lock().unwrap().retain(|key, _| {
+            let keep = exists(key);
+            if !keep {
+                removed.push(key.clone());
+            }
+            keep
+        });
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn meta(requested_at: Option<&str>) -> ObjectMeta {
+        ObjectMeta {
+            annotations: requested_at.map(|v| {
+                BTreeMap::from([(annotations::RESYNC_REQUESTED_AT.to_string(), v.to_string())])
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_requested_when_annotation_changes() {
+        let requests = ResyncRequests::new();
+
+        // Already set when first seen, e.g. at startup
+        assert!(!requests.requested("a", &meta(Some("2026-01-01T00:00:00Z"))));
+        assert!(!requests.requested("a", &meta(Some("2026-01-01T00:00:00Z"))));
+        assert!(requests.requested("a", &meta(Some("2026-01-02T00:00:00Z"))));
+
+        // Added to an object seen before
+        assert!(!requests.requested("b", &meta(None)));
+        assert!(requests.requested("b", &meta(Some("2026-01-01T00:00:00Z"))));
+
+        // Removing the annotation is not a request
+        assert!(!requests.requested("b", &meta(None)));
+    }
+
+    #[test]
+    fn test_forgotten_object_is_seen_again_as_new() {
+        let requests = ResyncRequests::new();
+        requests.requested("a", &meta(None));
+        requests.requested("b", &meta(None));
+        requests.requested("c", &meta(None));
+
+        requests.forget("a");
+        assert_eq!(requests.retain(|key| key != "b"), vec!["b".to_string()]);
+
+        // Recreated with the annotation: covered by the sync of the new object
+        assert!(!requests.requested("a", &meta(Some("2026-01-01T00:00:00Z"))));
+        assert!(!requests.requested("b", &meta(Some("2026-01-01T00:00:00Z"))));
+        assert!(requests.requested("c", &meta(Some("2026-01-01T00:00:00Z"))));
+        assert_eq!(requests.seen.lock().unwrap().len(), 3);
+    }
+}
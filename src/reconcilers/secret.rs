@@ -3,82 +3,170 @@
 
 //! Secret reconciler - watches Secrets and notifies sync manager of enabled ones.
 
-use crate::constants::annotations;
+use crate::config::Config;
 use crate::error::{OutriderError, Result};
+use crate::reconcilers::resync::ResyncRequests;
+use crate::sync::secrets::{
+    SecretMeta, SecretStores, fetch_secret, is_enabled, secret_key, secret_watcher_config,
+    source_secret_apis,
+};
 use crate::sync::{SyncEvent, SyncManagerHandle};
 use futures::StreamExt;
-use k8s_openapi::api::core::v1::Secret;
+use futures::future::join_all;
 use kube::{
-    runtime::{controller::Action, Controller},
-    Api, Client, ResourceExt,
+    Client, ResourceExt,
+    runtime::{
+        Controller, WatchStreamExt,
+        controller::Action,
+        metadata_watcher,
+        reflector::{ObjectRef, store::Writer},
+        watcher,
+    },
 };
-use kube_runtime::watcher::Config as WatcherConfig;
 use std::sync::Arc;
 use std::time::Duration;
-use tracing::{debug, error, warn};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
 
 pub struct SecretReconciler {
     client: Client,
+    config: Config,
     sync_handle: SyncManagerHandle,
+    resync_requests: ResyncRequests,
 }
 
 impl SecretReconciler {
-    pub fn new(client: Client, sync_handle: SyncManagerHandle) -> Self {
-        Self { client, sync_handle }
+    pub fn new(client: Client, config: Config, sync_handle: SyncManagerHandle) -> Self {
+        Self {
+            client,
+            config,
+            sync_handle,
+            resync_requests: ResyncRequests::new(),
+        }
     }
 
-    pub async fn run(self) -> anyhow::Result<()> {
-        let secrets: Api<Secret> = Api::all(self.client.clone());
+    /// Run the reconciler, keeping the stores behind `writers` populated with the
+    /// metadata of all watched secrets. Only metadata is watched, so payloads of
+    /// secrets that are not enabled never reach Outrider.
+    ///
+    /// One watch is started per source namespace, each feeding the writer at the
+    /// same position (see `SecretStores::new`). Stops once `shutdown` is cancelled
+    /// and running reconciliations have finished.
+    pub async fn run(
+        self,
+        writers: Vec<Writer<SecretMeta>>,
+        shutdown: CancellationToken,
+    ) -> anyhow::Result<()> {
+        let apis = source_secret_apis(&self.client, &self.config);
+        let watcher_config = secret_watcher_config(&self.config);
+        let health = self.sync_handle.health().clone();
         let context = Arc::new(self);
+        let caches = SecretStores::from(writers.iter().map(Writer::as_reader).collect::<Vec<_>>());
 
-        Controller::new(secrets, WatcherConfig::default())
-            .run(reconcile, error_policy, context)
-            .for_each(|res| async move {
-                match res {
-                    Ok(o) => debug!("Reconciled secret: {:?}", o),
-                    Err(e) => warn!("Reconciliation error: {:?}", e),
-                }
-            })
-            .await;
+        let controllers = apis.into_iter().zip(writers).map(|(secrets, writer)| {
+            let reader = writer.as_reader();
+            let component = format!("secret-watch {}", secrets.resource_url());
+            let health = health.clone();
+            let ctx = context.clone();
+            let caches = caches.clone();
+            let stream = metadata_watcher(secrets, watcher_config.clone())
+                .default_backoff()
+                .inspect(move |event| match event {
+                    Ok(_) => health.succeeded(&component),
+                    Err(_) => health.failed(&component),
+                })
+                .reflect(writer)
+                // Deleted secrets never reach the reconciler, so forget them here
+                .inspect(move |event| match event {
+                    Ok(watcher::Event::Delete(meta)) => {
+                        ctx.resync_requests.forget(&resync_key(meta))
+                    }
+                    Ok(watcher::Event::InitDone) => {
+                        ctx.resync_requests.retain(|key| {
+                            key.split_once('/').is_some_and(|(namespace, name)| {
+                                caches
+                                    .get(&ObjectRef::new(name).within(namespace))
+                                    .is_some()
+                            })
+                        });
+                    }
+                    _ => {}
+                })
+                .touched_objects();
+
+            Controller::for_stream(stream, reader)
+                .graceful_shutdown_on(shutdown.clone().cancelled_owned())
+                .run(reconcile, error_policy, context.clone())
+                .for_each(|res| async move {
+                    match res {
+                        Ok(o) => debug!("Reconciled secret: {:?}", o),
+                        Err(e) => warn!("Reconciliation error: {:?}", e),
+                    }
+                })
+        });
+        join_all(controllers).await;
 
+        if !shutdown.is_cancelled() {
+            health.exited("secret-controller");
+        }
         Ok(())
     }
 }
 
-async fn reconcile(secret: Arc<Secret>, ctx: Arc<SecretReconciler>) -> Result<Action> {
-    let name = secret.name_any();
-    let namespace = secret.namespace().unwrap_or_default();
+async fn reconcile(meta: Arc<SecretMeta>, ctx: Arc<SecretReconciler>) -> Result<Action> {
+    let name = meta.name_any();
+    let namespace = meta.namespace().unwrap_or_default();
 
     debug!("Reconciling secret: {}/{}", namespace, name);
 
-    // Check if secret has the enabled annotation
-    let is_enabled = secret
-        .metadata
-        .annotations
-        .as_ref()
-        .and_then(|a| a.get(annotations::ENABLED))
-        .is_some_and(|v| v == "true");
-
-    if !is_enabled {
+    // Check if secret has the enabled label or annotation
+    if !is_enabled(&meta.metadata) {
         debug!(
-            "Secret {}/{} does not have enabled annotation, skipping",
+            "Secret {}/{} does not have enabled label or annotation, skipping",
             namespace, name
         );
+        ctx.resync_requests.forget(&resync_key(&meta));
         return Ok(Action::await_change());
     }
+    let resync_requested = ctx
+        .resync_requests
+        .requested(&resync_key(&meta), &meta.metadata);
+
+    // Only enabled secrets are fetched in full
+    let Some(secret) = fetch_secret(&ctx.client, &meta).await? else {
+        debug!("Secret {}/{} was deleted, skipping", namespace, name);
+        return Ok(Action::await_change());
+    };
 
     // Notify the sync manager about the secret change
-    ctx.sync_handle
-        .send(SyncEvent::SecretChanged {
-            secret: (*secret).clone(),
-        })
-        .await;
+    if resync_requested {
+        info!(
+            "Resync of secret {} requested through its annotation",
+            secret_key(&secret)
+        );
+        ctx.sync_handle
+            .send(SyncEvent::ResyncSecret { secret })
+            .await;
+    } else {
+        ctx.sync_handle
+            .send(SyncEvent::SecretChanged { secret })
+            .await;
+    }
 
     Ok(Action::await_change())
 }
 
+/// Key of a secret in the resync requests, matching `secret_key`
+fn resync_key(meta: &SecretMeta) -> String {
+    format!(
+        "{}/{}",
+        meta.namespace().unwrap_or_default(),
+        meta.name_any()
+    )
+}
+
 fn error_policy(
-    _secret: Arc<Secret>,
+    _meta: Arc<SecretMeta>,
     error: &OutriderError,
     _ctx: Arc<SecretReconciler>,
 ) -> Action {
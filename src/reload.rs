@@ -0,0 +1,96 @@
+// Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reloading of the config file when it changes or on SIGHUP.
+
+use crate::config::Config;
+use crate::constants::reload::POLL_INTERVAL_SECS;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::signal::unix::{SignalKind, signal};
+use tokio::sync::watch;
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+/// Watches the config file and publishes the reloaded configuration.
+/// An invalid file is logged and the current configuration is kept.
+pub struct ConfigReloader {
+    path: PathBuf,
+    config: watch::Sender<Config>,
+    /// Contents of the file when it was last loaded
+    contents: Option<String>,
+}
+
+impl ConfigReloader {
+    pub fn new(path: PathBuf, config: watch::Sender<Config>) -> Self {
+        let contents = fs::read_to_string(&path).ok();
+        Self {
+            path,
+            config,
+            contents,
+        }
+    }
+
+    /// Reload on SIGHUP, or when the file contents change, until `shutdown` is cancelled
+    pub async fn run(mut self, shutdown: CancellationToken) -> anyhow::Result<()> {
+        let mut hangup = signal(SignalKind::hangup())?;
+        info!(
+            "Reloading configuration from {} on change or SIGHUP",
+            self.path.display()
+        );
+
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => return Ok(()),
+                _ = hangup.recv() => {
+                    info!("Received SIGHUP, reloading configuration");
+                    self.contents = fs::read_to_string(&self.path).ok();
+                    self.reload();
+                }
+                _ = sleep(Duration::from_secs(POLL_INTERVAL_SECS)) => {
+                    // Kubernetes updates mounted ConfigMaps by swapping a symlink,
+                    // so compare contents rather than relying on file events
+                    let contents = fs::read_to_string(&self.path).ok();
+                    if contents != self.contents {
+                        info!("Configuration file changed, reloading");
+                        self.contents = contents;
+                        self.reload();
+                    }
+                }
+            }
+        }
+    }
+
+    fn reload(&self) {
+        let new = match Config::load() {
+            Ok(new) => new,
+            Err(e) => {
+                error!("Invalid configuration, keeping the current one: {:#}", e);
+                return;
+            }
+        };
+
+        let (config, ignored) = self.config.borrow().reload(new);
+        if !ignored.is_empty() {
+            warn!(
+                "Changes to {} only take effect after a restart",
+                ignored.join(", ")
+            );
+        }
+
+        let changed = self.config.send_if_modified(|current| {
+            if *current == config {
+                return false;
+            }
+            *current = config;
+            true
+        });
+        if changed {
+            info!("Configuration reloaded");
+        } else {
+            info!("Configuration unchanged");
+        }
+    }
+}
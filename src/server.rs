@@ -0,0 +1,168 @@
+// Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
+// SPDX-License-Identifier: Apache-2.0
+
+//! HTTP server exposing operator metrics, health probes and the admin API.
+
+use crate::admin::{self, AdminState};
+use crate::constants::LOCAL_CLUSTER;
+use crate::sync::SyncManagerHandle;
+use axum::{
+    Router,
+    extract::State,
+    http::{StatusCode, header::CONTENT_TYPE},
+    response::IntoResponse,
+    routing::get,
+};
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+/// Serve the HTTP endpoints on all interfaces until `shutdown` is cancelled.
+/// The admin API is only served when `admin` is set.
+pub async fn serve(
+    port: u16,
+    handle: SyncManagerHandle,
+    admin: Option<AdminState>,
+    shutdown: CancellationToken,
+) -> anyhow::Result<()> {
+    let listener = TcpListener::bind(("0.0.0.0", port)).await?;
+    info!("Serving metrics and health probes on port {}", port);
+    let mut router = router(handle);
+    if let Some(admin) = admin {
+        info!("Serving the admin API on port {}", port);
+        router = router.merge(admin::router(admin));
+    }
+    axum::serve(listener, router)
+        .with_graceful_shutdown(shutdown.cancelled_owned())
+        .await?;
+    Ok(())
+}
+
+fn router(handle: SyncManagerHandle) -> Router {
+    Router::new()
+        .route("/metrics", get(render_metrics))
+        .route("/healthz", get(liveness))
+        .route("/readyz", get(readiness))
+        .with_state(handle)
+}
+
+async fn render_metrics(State(handle): State<SyncManagerHandle>) -> impl IntoResponse {
+    (
+        [(CONTENT_TYPE, prometheus::TEXT_FORMAT)],
+        handle.metrics().encode(),
+    )
+}
+
+async fn liveness(State(handle): State<SyncManagerHandle>) -> impl IntoResponse {
+    match handle.health().check_live() {
+        Ok(()) => (StatusCode::OK, "ok".to_string()),
+        Err(reason) => {
+            warn!("Liveness check failed: {}", reason);
+            (StatusCode::SERVICE_UNAVAILABLE, reason)
+        }
+    }
+}
+
+/// Not ready until the initial sync is done, nor while the manager cluster
+/// lacks permissions Outrider needs
+async fn readiness(State(handle): State<SyncManagerHandle>) -> impl IntoResponse {
+    if !handle.health().is_ready() {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "initial sync not complete".to_string(),
+        );
+    }
+    let missing = handle.status().missing_permissions(LOCAL_CLUSTER);
+    if !missing.is_empty() {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            format!(
+                "missing permissions in the manager cluster: {}",
+                missing.join(", ")
+            ),
+        );
+    }
+    (StatusCode::OK, "ok".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{SecretStores, SyncManager};
+    use crate::test_utils::{MockService, test_config};
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use kube::runtime::reflector;
+    use tower::ServiceExt;
+
+    fn make_handle() -> SyncManagerHandle {
+        let config = test_config();
+        let (secrets, _) = SecretStores::new(&config);
+        let (clusters, _) = reflector::store();
+        let (_, config) = tokio::sync::watch::channel(config);
+        let (_, handle) =
+            SyncManager::new(MockService::new().into_client(), config, secrets, clusters);
+        handle
+    }
+
+    async fn get(handle: &SyncManagerHandle, path: &str) -> (StatusCode, String) {
+        let response = router(handle.clone())
+            .oneshot(Request::get(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8_lossy(&body).to_string())
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint() {
+        let handle = make_handle();
+        handle.metrics().set_queue_depth(3);
+
+        let (status, body) = get(&handle, "/metrics").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("outrider_event_queue_depth 3"));
+    }
+
+    #[tokio::test]
+    async fn test_readiness_follows_initial_sync() {
+        let handle = make_handle();
+        handle.health().set_crd_ready();
+        assert_eq!(
+            get(&handle, "/readyz").await.0,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        handle.health().set_initial_sync_done();
+        assert_eq!(get(&handle, "/readyz").await.0, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_reports_missing_manager_permissions() {
+        let handle = make_handle();
+        handle.health().set_crd_ready();
+        handle.health().set_initial_sync_done();
+        handle
+            .status()
+            .set_missing_permissions(LOCAL_CLUSTER, vec!["list secrets cluster-wide".to_string()]);
+
+        let (status, body) = get(&handle, "/readyz").await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(body.contains("list secrets cluster-wide"));
+    }
+
+    #[tokio::test]
+    async fn test_liveness_fails_when_component_exits() {
+        let handle = make_handle();
+        assert_eq!(get(&handle, "/healthz").await.0, StatusCode::OK);
+
+        handle.health().exited("cluster-controller");
+        let (status, body) = get(&handle, "/healthz").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(body.contains("cluster-controller"));
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Cluster listing and filtering utilities
+
+use crate::error::Result;
+use crate::types::cluster::Cluster;
+use kube::{Api, Client, api::ListParams, runtime::reflector::Store};
+use tracing::instrument;
+
+/// Get all ready Rancher clusters (excluding the local cluster) from the API server
+#[instrument(skip(client))]
+pub async fn get_ready_clusters(client: &Client) -> Result<Vec<Cluster>> {
+    let clusters: Api<Cluster> = Api::all(client.clone());
+    let cluster_list = clusters.list(&ListParams::default()).await?;
+
+    Ok(cluster_list
+        .items
+        .into_iter()
+        .filter(is_target_cluster)
+        .collect())
+}
+
+/// Get all ready Rancher clusters (excluding the local cluster) from the store
+pub fn ready_clusters(store: &Store<Cluster>) -> Vec<Cluster> {
+    store
+        .state()
+        .into_iter()
+        .filter(|c| is_target_cluster(c))
+        .map(|c| (*c).clone())
+        .collect()
+}
+
+/// Whether secrets should be synced to this cluster
+fn is_target_cluster(cluster: &Cluster) -> bool {
+    cluster.is_ready() && !cluster.is_local()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::make_cluster;
+    use kube::ResourceExt;
+    use kube::runtime::{reflector, watcher};
+
+    #[test]
+    fn test_ready_clusters_from_store() {
+        let (store, mut writer) = reflector::store();
+        for cluster in [
+            make_cluster("ready", true),
+            make_cluster("not-ready", false),
+            make_cluster("local", true),
+        ] {
+            writer.apply_watcher_event(&watcher::Event::Apply(cluster));
+        }
+
+        let clusters = ready_clusters(&store);
+
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].name_any(), "ready");
+    }
+}
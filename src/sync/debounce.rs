@@ -0,0 +1,157 @@
+// Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Debouncing of repeated events for the same object.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Holds the latest value per key until its debounce window has passed.
+/// The window starts at the first event for a key, so a steady stream of
+/// updates is still flushed at least once per window.
+pub struct Debouncer<T> {
+    window: Duration,
+    pending: BTreeMap<String, (T, Instant)>,
+}
+
+impl<T> Debouncer<T> {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Change the window for keys that are not pending yet
+    pub fn set_window(&mut self, window: Duration) {
+        self.window = window;
+    }
+
+    /// Store the latest value for a key.
+    /// Returns true if it replaced a pending value (the events were coalesced).
+    pub fn push(&mut self, key: String, value: T, now: Instant) -> bool {
+        match self.pending.get_mut(&key) {
+            Some((pending, _)) => {
+                *pending = value;
+                true
+            }
+            None => {
+                self.pending.insert(key, (value, now + self.window));
+                false
+            }
+        }
+    }
+
+    /// Drop the pending value for a key, returning true if there was one
+    pub fn remove(&mut self, key: &str) -> bool {
+        self.pending.remove(key).is_some()
+    }
+
+    /// Drop all pending values
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Take all values whose debounce window has passed
+    pub fn take_due(&mut self, now: Instant) -> Vec<T> {
+        let due: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, (_, at))| *at <= now)
+            .map(|(k, _)| k.clone())
+            .collect();
+
+        due.into_iter()
+            .filter_map(|k| self.pending.remove(&k).map(|(v, _)| v))
+            .collect()
+    }
+
+    /// When the earliest pending value is due
+    pub fn next_due(&self) -> Option<Instant> {
+        self.pending.values().map(|(_, at)| *at).min()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_coalesces_same_key() {
+        let mut debouncer = Debouncer::new(Duration::from_millis(100));
+        let now = Instant::now();
+
+        assert!(!debouncer.push("a".to_string(), 1, now));
+        assert!(debouncer.push("a".to_string(), 2, now));
+        assert!(!debouncer.push("b".to_string(), 3, now));
+
+        assert_eq!(debouncer.len(), 2);
+        assert_eq!(
+            debouncer.take_due(now + Duration::from_millis(100)),
+            vec![2, 3]
+        );
+        assert!(debouncer.is_empty());
+    }
+
+    #[test]
+    fn test_values_are_held_until_window_passes() {
+        let mut debouncer = Debouncer::new(Duration::from_millis(100));
+        let now = Instant::now();
+
+        debouncer.push("a".to_string(), 1, now);
+
+        assert!(
+            debouncer
+                .take_due(now + Duration::from_millis(50))
+                .is_empty()
+        );
+        assert_eq!(debouncer.next_due(), Some(now + Duration::from_millis(100)));
+        assert_eq!(
+            debouncer.take_due(now + Duration::from_millis(100)),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn test_window_starts_at_first_event() {
+        let mut debouncer = Debouncer::new(Duration::from_millis(100));
+        let now = Instant::now();
+
+        debouncer.push("a".to_string(), 1, now);
+        debouncer.push("a".to_string(), 2, now + Duration::from_millis(90));
+
+        assert_eq!(
+            debouncer.take_due(now + Duration::from_millis(100)),
+            vec![2]
+        );
+    }
+
+    #[test]
+    fn test_zero_window_is_due_immediately() {
+        let mut debouncer = Debouncer::new(Duration::ZERO);
+        let now = Instant::now();
+
+        debouncer.push("a".to_string(), 1, now);
+        assert_eq!(debouncer.take_due(now), vec![1]);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut debouncer = Debouncer::new(Duration::from_millis(100));
+        let now = Instant::now();
+
+        debouncer.push("a".to_string(), 1, now);
+        assert!(debouncer.remove("a"));
+        assert!(!debouncer.remove("a"));
+        assert!(debouncer.next_due().is_none());
+    }
+}
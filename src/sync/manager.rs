@@ -4,15 +4,32 @@
 //! Central coordinator for syncing secrets to clusters.
 
 use crate::config::Config;
-use crate::sync::secrets::{copy_secret_to_cluster, get_enabled_secrets};
+use crate::constants::health::HEARTBEAT_INTERVAL_SECS;
+use crate::constants::state::SAVE_INTERVAL_SECS;
+use crate::health::Health;
+use crate::metrics::Metrics;
+use crate::sync::clusters::ready_clusters;
+use crate::sync::debounce::Debouncer;
+use crate::sync::secrets::{
+    SecretStores, fetch_enabled_secrets, fetch_secret, is_enabled, secret_key,
+};
+use crate::sync::state::{self, ResyncMarks, StateStore};
+use crate::sync::status::SyncStatus;
+use crate::sync::worker::{ClusterWorker, WorkerContext};
 use crate::types::cluster::Cluster;
+use futures::future::join_all;
 use k8s_openapi::api::core::v1::Secret;
-use kube::{api::ListParams, Api, Client, ResourceExt};
-use std::collections::HashSet;
-use std::sync::atomic::{AtomicBool, Ordering};
+use kube::{
+    Client, ResourceExt,
+    runtime::reflector::{ObjectRef, Store},
+};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use tokio::sync::{mpsc, RwLock};
-use tracing::{debug, error, info, instrument};
+use std::time::Duration;
+use tokio::sync::{RwLock, mpsc, watch};
+use tokio::time::{Instant, sleep, sleep_until, timeout};
+use tokio_util::sync::CancellationToken;
+use tracing::{Span, debug, error, info, instrument, warn};
 
 /// Events that reconcilers send to the SyncManager
 #[derive(Debug, Clone)]
@@ -23,88 +40,417 @@ pub enum SyncEvent {
     ClusterBecameReady { cluster: Cluster },
     /// A cluster is no longer ready (no action needed, just logged)
     ClusterBecameNotReady { name: String },
+    /// Forget the recorded sync state of a secret and copy it to all ready clusters again
+    ResyncSecret { secret: Secret },
+    /// Forget that a ready cluster was synced and copy all enabled secrets to it again
+    ResyncCluster { cluster: Cluster },
 }
 
 /// Central coordinator for syncing secrets to clusters.
-/// Receives events from reconcilers and performs the actual sync work.
+/// Receives events from reconcilers and hands the actual sync work to
+/// per-cluster workers, so the event loop never waits on a downstream cluster.
 pub struct SyncManager {
-    client: Client,
-    config: Config,
-    event_rx: mpsc::Receiver<SyncEvent>,
-    initial_sync_done: Arc<AtomicBool>,
+    /// Metadata of secrets in the manager cluster, kept up to date by the SecretReconciler.
+    /// Full secrets are fetched on demand.
+    secrets: SecretStores,
+    /// Rancher clusters, kept up to date by the ClusterReconciler
+    clusters: Store<Cluster>,
+    /// Current configuration, updated when the config file is reloaded
+    config: watch::Receiver<Config>,
+    /// Events together with the span they were sent from
+    event_rx: mpsc::Receiver<(SyncEvent, Span)>,
+    health: Health,
     /// Tracks clusters that have already received their initial secret sync.
     /// When a cluster becomes ready for the first time (or after being not-ready),
     /// it gets a full sync and is added here. Updates to already-synced clusters
     /// don't trigger re-syncs.
     synced_clusters: Arc<RwLock<HashSet<String>>>,
+    /// Work queue and worker for each ready cluster
+    workers: Arc<RwLock<HashMap<String, ClusterWorker>>>,
+    worker_ctx: WorkerContext,
+    /// Secret changes waiting out their debounce window, keyed by `namespace/name`,
+    /// together with when the change was received and the span it was sent from
+    pending_secrets: Debouncer<(Secret, Instant, Span)>,
+    /// Ready clusters waiting out their debounce window, keyed by name
+    pending_clusters: Debouncer<(Cluster, Span)>,
+    /// Where the sync state is persisted across restarts, if anywhere
+    state: Option<StateStore>,
+}
+
+/// Why the manager stopped leading
+enum LeadEnd {
+    LostLeadership,
+    Shutdown,
 }
 
 /// Handle to send events to the SyncManager
 #[derive(Clone)]
 pub struct SyncManagerHandle {
-    event_tx: mpsc::Sender<SyncEvent>,
+    event_tx: mpsc::Sender<(SyncEvent, Span)>,
+    status: SyncStatus,
+    metrics: Metrics,
+    health: Health,
+    synced_clusters: Arc<RwLock<HashSet<String>>>,
 }
 
 impl SyncManagerHandle {
+    /// Last known sync outcome for each (cluster, secret) pair
+    pub fn status(&self) -> &SyncStatus {
+        &self.status
+    }
+
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    pub fn health(&self) -> &Health {
+        &self.health
+    }
+
+    /// Clusters that received their initial sync of all enabled secrets
+    pub async fn synced_clusters(&self) -> HashSet<String> {
+        self.synced_clusters.read().await.clone()
+    }
+
+    /// Send an event to the SyncManager. The current span becomes the parent
+    /// of the spans handling the event, so a trace follows it to every cluster.
     pub async fn send(&self, event: SyncEvent) {
-        if let Err(e) = self.event_tx.send(event).await {
+        if let Err(e) = self.event_tx.send((event, Span::current())).await {
             error!("Failed to send event to SyncManager: {}", e);
         }
+        self.metrics
+            .set_queue_depth(self.event_tx.max_capacity() - self.event_tx.capacity());
     }
 }
 
 impl SyncManager {
-    pub fn new(client: Client, config: Config) -> (Self, SyncManagerHandle) {
+    pub fn new(
+        client: Client,
+        config: watch::Receiver<Config>,
+        secrets: SecretStores,
+        clusters: Store<Cluster>,
+    ) -> (Self, SyncManagerHandle) {
         let (event_tx, event_rx) = mpsc::channel(256);
+        let status = SyncStatus::new();
+        let metrics = Metrics::new();
+        let health = Health::new();
+        let synced_clusters = Arc::new(RwLock::new(HashSet::new()));
+        let debounce = config.borrow().debounce;
+        let state = config
+            .borrow()
+            .state_config_map
+            .as_ref()
+            .map(|state_config_map| StateStore::new(client.clone(), state_config_map));
+        let worker_ctx = WorkerContext::new(
+            client,
+            config.clone(),
+            secrets.clone(),
+            status.clone(),
+            metrics.clone(),
+        );
 
         let manager = Self {
-            client,
+            secrets,
+            clusters,
             config,
             event_rx,
-            initial_sync_done: Arc::new(AtomicBool::new(false)),
-            synced_clusters: Arc::new(RwLock::new(HashSet::new())),
+            health: health.clone(),
+            synced_clusters: synced_clusters.clone(),
+            workers: Arc::new(RwLock::new(HashMap::new())),
+            worker_ctx,
+            pending_secrets: Debouncer::new(debounce),
+            pending_clusters: Debouncer::new(debounce),
+            state,
         };
 
-        let handle = SyncManagerHandle { event_tx };
+        let handle = SyncManagerHandle {
+            event_tx,
+            status,
+            metrics,
+            health,
+            synced_clusters,
+        };
         (manager, handle)
     }
 
-    pub async fn run(mut self) -> anyhow::Result<()> {
-        info!("SyncManager started, performing initial sync...");
-        self.initial_sync().await;
-        info!("Initial sync complete, listening for events...");
+    /// Run the manager. Only while `leader` is true are secrets synced; standby
+    /// replicas discard events and rely on their warm caches when taking over.
+    ///
+    /// When `shutdown` is cancelled, in-flight work is drained within the
+    /// configured shutdown timeout before returning.
+    pub async fn run(
+        self,
+        leader: watch::Receiver<bool>,
+        shutdown: CancellationToken,
+    ) -> anyhow::Result<()> {
+        let health = self.health.clone();
+        let result = self.run_loop(leader, shutdown).await;
+        health.exited("sync-manager");
+        result
+    }
 
-        while let Some(event) = self.event_rx.recv().await {
-            self.handle_event(event).await;
+    async fn run_loop(
+        mut self,
+        mut leader: watch::Receiver<bool>,
+        shutdown: CancellationToken,
+    ) -> anyhow::Result<()> {
+        info!("SyncManager started, waiting for caches...");
+
+        // Keep receiving events while the caches load, so reconcilers are never
+        // blocked on a full channel and no change is lost. Buffered events are
+        // replayed after the initial sync.
+        let mut buffered = Vec::new();
+        let caches_ready = wait_for_caches(self.secrets.clone(), self.clusters.clone());
+        tokio::pin!(caches_ready);
+
+        loop {
+            tokio::select! {
+                ready = &mut caches_ready => {
+                    ready?;
+                    break;
+                }
+                event = self.event_rx.recv() => match event {
+                    Some(traced) => buffered.push(traced),
+                    None => return Ok(()),
+                },
+                _ = shutdown.cancelled() => return Ok(()),
+            }
         }
 
-        Ok(())
+        loop {
+            if !*leader.borrow_and_update() {
+                // The caches hold the latest state, so the initial sync on
+                // taking over covers anything discarded while standing by
+                buffered.clear();
+                info!("Caches ready, standing by until elected leader...");
+                // A warm standby is ready to take over
+                self.health.set_initial_sync_done();
+                if !self.stand_by(&mut leader, &shutdown).await {
+                    return Ok(());
+                }
+            }
+
+            info!("Caches ready, performing initial sync...");
+            self.restore_state().await;
+            self.initial_sync().await;
+
+            if !buffered.is_empty() {
+                info!(
+                    "Replaying {} event(s) received during initial sync",
+                    buffered.len()
+                );
+            }
+            for (event, span) in buffered.drain(..) {
+                self.debounce_event(event, span).await;
+            }
+
+            info!("Initial sync complete, listening for events...");
+            match self.lead(&mut leader, &shutdown).await {
+                LeadEnd::LostLeadership => self.step_down().await,
+                LeadEnd::Shutdown => {
+                    self.drain().await;
+                    return Ok(());
+                }
+            }
+        }
     }
 
-    #[instrument(skip(self))]
-    async fn initial_sync(&self) {
-        let clusters = match self.get_ready_clusters().await {
-            Ok(c) => c,
-            Err(e) => {
-                error!("Failed to get ready clusters for initial sync: {}", e);
-                return;
+    /// Discard events until this replica becomes the leader.
+    /// Returns false on shutdown or if the event or leadership channel closed.
+    async fn stand_by(
+        &mut self,
+        leader: &mut watch::Receiver<bool>,
+        shutdown: &CancellationToken,
+    ) -> bool {
+        loop {
+            self.health.beat("sync-manager");
+
+            tokio::select! {
+                _ = shutdown.cancelled() => return false,
+                changed = leader.changed() => {
+                    if changed.is_err() {
+                        return false;
+                    }
+                    if *leader.borrow_and_update() {
+                        return true;
+                    }
+                }
+                event = self.event_rx.recv() => {
+                    if event.is_none() {
+                        return false;
+                    }
+                    self.worker_ctx.metrics.set_queue_depth(self.event_rx.len());
+                }
+                _ = sleep(Duration::from_secs(HEARTBEAT_INTERVAL_SECS)) => {}
             }
-        };
+        }
+    }
 
-        info!("Found {} ready clusters", clusters.len());
+    /// Handle events while this replica is the leader, until leadership is
+    /// lost or the operator shuts down
+    async fn lead(
+        &mut self,
+        leader: &mut watch::Receiver<bool>,
+        shutdown: &CancellationToken,
+    ) -> LeadEnd {
+        let save_interval = Duration::from_secs(SAVE_INTERVAL_SECS);
+        let mut save_at = Instant::now() + save_interval;
+        loop {
+            // Wake up at least once per heartbeat interval, so a stalled loop is noticed
+            self.health.beat("sync-manager");
+            let heartbeat_at = Instant::now() + Duration::from_secs(HEARTBEAT_INTERVAL_SECS);
+            let wake_at = self
+                .next_flush()
+                .map_or(heartbeat_at, |flush_at| flush_at.min(heartbeat_at))
+                .min(save_at);
+
+            tokio::select! {
+                _ = shutdown.cancelled() => return LeadEnd::Shutdown,
+                event = self.event_rx.recv() => match event {
+                    Some((event, span)) => {
+                        self.worker_ctx.metrics.set_queue_depth(self.event_rx.len());
+                        self.debounce_event(event, span).await
+                    }
+                    None => return LeadEnd::Shutdown,
+                },
+                // Without an elector there is no way to know we still lead
+                changed = leader.changed() => {
+                    if changed.is_err() || !*leader.borrow_and_update() {
+                        return LeadEnd::LostLeadership;
+                    }
+                }
+                Ok(()) = self.config.changed() => self.apply_config(),
+                _ = sleep_until(wake_at) => {}
+            }
 
-        let secrets = match get_enabled_secrets(&self.client).await {
-            Ok(s) => s,
-            Err(e) => {
-                error!("Failed to get enabled secrets for initial sync: {}", e);
-                return;
+            self.flush_due_events().await;
+            if Instant::now() >= save_at {
+                self.save_state().await;
+                save_at = Instant::now() + save_interval;
             }
+        }
+    }
+
+    /// Hand all debounced changes to the workers and let them finish within the
+    /// shutdown timeout. Secrets that were not applied in time are logged; they
+    /// are synced again by the initial sync on the next start.
+    async fn drain(&mut self) {
+        let far_future = Instant::now() + Duration::from_secs(3600);
+        let clusters = self.pending_clusters.take_due(far_future);
+        self.handle_clusters_ready(clusters).await;
+        for (secret, changed_at, span) in self.pending_secrets.take_due(far_future) {
+            self.handle_secret_changed(&secret, changed_at, &span).await;
+        }
+
+        let mut workers = std::mem::take(&mut *self.workers.write().await);
+        let shutdown_timeout = self.config.borrow().shutdown_timeout;
+        info!(
+            "Shutting down, draining {} cluster worker(s) within {:?}",
+            workers.len(),
+            shutdown_timeout
+        );
+
+        for worker in workers.values() {
+            worker.drain();
+        }
+        let drained = timeout(
+            shutdown_timeout,
+            join_all(workers.values_mut().map(|worker| worker.stopped())),
+        )
+        .await;
+        if drained.is_err() {
+            warn!(
+                "Cluster workers did not finish within {:?}",
+                shutdown_timeout
+            );
+        }
+
+        for (cluster, worker) in &workers {
+            let unfinished = worker.unfinished();
+            if !unfinished.is_empty() {
+                warn!(
+                    "Secret(s) {} not synced to cluster '{}' before shutdown, they will be synced on the next start",
+                    unfinished.join(", "),
+                    cluster
+                );
+            }
+        }
+        self.save_state().await;
+        // Dropping the workers aborts any copy still in flight
+    }
+
+    /// Apply a reloaded configuration to the debounce windows and the
+    /// concurrency limit. Workers read the other settings for every copy.
+    fn apply_config(&mut self) {
+        let config = self.config.borrow_and_update().clone();
+        debug!("Applying reloaded configuration");
+        self.pending_secrets.set_window(config.debounce);
+        self.pending_clusters.set_window(config.debounce);
+        self.worker_ctx
+            .set_max_concurrent_syncs(config.max_concurrent_syncs);
+    }
+
+    /// Record the persisted sync state, so the initial sync skips secrets that
+    /// did not change since they were last synced to a cluster
+    async fn restore_state(&mut self) {
+        let Some(store) = &mut self.state else {
+            return;
         };
+        match store.load().await {
+            Ok(persisted) => {
+                let marks = ResyncMarks::from_caches(&self.secrets, &self.clusters);
+                let restored = state::restore(persisted, &self.worker_ctx.status, &marks);
+                info!("Restored sync state of {} secret copies", restored);
+            }
+            Err(e) => warn!(
+                "Failed to load sync state, syncing all secrets to all clusters: {}",
+                e
+            ),
+        }
+    }
+
+    /// Persist the recorded sync state. Nothing is saved in a dry run, as its
+    /// syncs were only validated.
+    async fn save_state(&mut self) {
+        if self.config.borrow().dry_run {
+            return;
+        }
+        let Some(store) = &mut self.state else {
+            return;
+        };
+        let marks = ResyncMarks::from_caches(&self.secrets, &self.clusters);
+        let persisted = state::capture(&self.worker_ctx.status, &marks);
+        if let Err(e) = store.save(persisted).await {
+            warn!("Failed to save sync state: {}", e);
+            self.worker_ctx.metrics.record_state_save_failure();
+        }
+    }
+
+    /// Stop all work after losing leadership, so only the new leader writes downstream
+    async fn step_down(&mut self) {
+        warn!("No longer the leader, stopping all cluster workers");
+        self.workers.write().await.clear();
+        self.synced_clusters.write().await.clear();
+        self.pending_secrets.clear();
+        self.pending_clusters.clear();
+        self.worker_ctx.status.clear();
+        self.worker_ctx.metrics.forget_cluster_backlog(None);
+        self.update_cluster_metrics().await;
+    }
+
+    /// Queue all enabled secrets for all ready clusters, based on the current cache contents
+    #[instrument(skip(self))]
+    async fn initial_sync(&self) {
+        let clusters = ready_clusters(&self.clusters);
+        info!("Found {} ready clusters", clusters.len());
+
+        let secrets = self.fetch_enabled_secrets().await;
 
         info!("Found {} enabled secrets", secrets.len());
 
-        for secret in &secrets {
-            self.sync_secret_to_clusters(secret, &clusters).await;
+        let now = Instant::now();
+        for cluster in &clusters {
+            self.enqueue_secrets(cluster, &secrets, now).await;
         }
 
         // Mark all ready clusters as synced
@@ -112,120 +458,277 @@ impl SyncManager {
         for cluster in &clusters {
             synced.insert(cluster.name_any());
         }
+        self.worker_ctx
+            .metrics
+            .set_clusters(clusters.len(), synced.len());
 
-        self.initial_sync_done.store(true, Ordering::SeqCst);
+        self.health.set_initial_sync_done();
     }
 
-    async fn handle_event(&self, event: SyncEvent) {
+    /// Buffer an event until its debounce window has passed, coalescing it
+    /// with any pending event for the same secret or cluster.
+    /// `span` is where the event was sent from; a coalesced event keeps the latest.
+    async fn debounce_event(&mut self, event: SyncEvent, span: Span) {
         debug!("Handling event: {:?}", event);
+        let now = Instant::now();
 
-        match event {
+        let coalesced = match event {
             SyncEvent::SecretChanged { secret } => {
-                self.handle_secret_changed(&secret).await;
+                self.pending_secrets
+                    .push(secret_key(&secret), (secret, now, span), now)
             }
             SyncEvent::ClusterBecameReady { cluster } => {
-                self.handle_cluster_ready(&cluster).await;
+                self.pending_clusters
+                    .push(cluster.name_any(), (cluster, span), now)
             }
             SyncEvent::ClusterBecameNotReady { name } => {
-                self.handle_cluster_not_ready(&name).await;
+                // A pending ready event for this cluster is now stale
+                let coalesced = self.pending_clusters.remove(&name);
+                self.handle_cluster_not_ready(&name, &span).await;
+                coalesced
             }
+            // A forced resync supersedes a pending change to the same object
+            SyncEvent::ResyncSecret { secret } => {
+                let coalesced = self.pending_secrets.remove(&secret_key(&secret));
+                self.handle_secret_resync(&secret, &span).await;
+                coalesced
+            }
+            SyncEvent::ResyncCluster { cluster } => {
+                let coalesced = self.pending_clusters.remove(&cluster.name_any());
+                self.handle_cluster_resync(&cluster, &span).await;
+                coalesced
+            }
+        };
+
+        if coalesced {
+            self.worker_ctx.metrics.record_coalesced_event();
+            debug!("Coalesced event with pending event for the same object");
         }
     }
 
-    #[instrument(skip(self, secret), fields(secret = %format!("{}/{}", secret.namespace().unwrap_or_default(), secret.name_any())))]
-    async fn handle_secret_changed(&self, secret: &Secret) {
-        // Skip if initial sync hasn't completed yet
-        if !self.initial_sync_done.load(Ordering::SeqCst) {
-            debug!("Skipping secret change, initial sync not complete");
-            return;
+    /// When the earliest buffered event is due
+    fn next_flush(&self) -> Option<Instant> {
+        match (
+            self.pending_secrets.next_due(),
+            self.pending_clusters.next_due(),
+        ) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
         }
+    }
 
-        info!("Secret changed, syncing to all ready clusters");
+    /// Handle all buffered events whose debounce window has passed
+    async fn flush_due_events(&mut self) {
+        let now = Instant::now();
 
-        let clusters = match self.get_ready_clusters().await {
-            Ok(c) => c,
-            Err(e) => {
-                error!("Failed to get ready clusters: {}", e);
-                return;
-            }
-        };
+        let clusters = self.pending_clusters.take_due(now);
+        self.handle_clusters_ready(clusters).await;
 
-        self.sync_secret_to_clusters(secret, &clusters).await;
+        for (secret, changed_at, span) in self.pending_secrets.take_due(now) {
+            self.handle_secret_changed(&secret, changed_at, &span).await;
+        }
     }
 
-    #[instrument(skip(self, cluster), fields(cluster = %cluster.name_any()))]
-    async fn handle_cluster_ready(&self, cluster: &Cluster) {
-        let cluster_name = cluster.name_any();
-
-        // Check if this cluster has already been synced
-        if self.synced_clusters.read().await.contains(&cluster_name) {
-            debug!(
-                "Cluster '{}' already synced, skipping secret sync on update",
-                cluster_name
-            );
+    #[instrument(
+        parent = parent,
+        skip(self, secret, parent),
+        fields(secret = %secret_key(secret))
+    )]
+    async fn handle_secret_changed(&self, secret: &Secret, changed_at: Instant, parent: &Span) {
+        // The event may be older than what the cache has seen since, e.g. when it
+        // was buffered during the initial sync, so always sync the latest version
+        let Some(secret) = self.latest_secret(secret).await else {
+            debug!("Secret was deleted or disabled since the event, skipping");
             return;
+        };
+
+        info!("Secret changed, queueing sync to all ready clusters");
+
+        for cluster in &ready_clusters(&self.clusters) {
+            self.enqueue_secrets(cluster, std::slice::from_ref(&secret), changed_at)
+                .await;
         }
+    }
+
+    #[instrument(
+        parent = parent,
+        skip(self, secret, parent),
+        fields(secret = %secret_key(secret))
+    )]
+    async fn handle_secret_resync(&self, secret: &Secret, parent: &Span) {
+        info!("Resync requested, forgetting the synced state of the secret");
+        // Without a known hash every cluster is contacted again
+        self.worker_ctx.status.remove_secret(&secret_key(secret));
+        self.handle_secret_changed(secret, Instant::now(), &Span::current())
+            .await;
+    }
 
-        info!("New cluster became ready, syncing all enabled secrets");
+    /// The latest version of a secret, if it still exists and is enabled.
+    /// The secret is only fetched again when the cache has seen a newer version.
+    async fn latest_secret(&self, secret: &Secret) -> Option<Secret> {
+        let obj_ref = ObjectRef::new(&secret.name_any()).within(&secret.namespace()?);
+        let latest = self.secrets.get(&obj_ref)?;
+        if !is_enabled(&latest.metadata) {
+            return None;
+        }
+        if latest.resource_version() == secret.resource_version() {
+            return Some(secret.clone());
+        }
 
-        let secrets = match get_enabled_secrets(&self.client).await {
-            Ok(s) => s,
+        match fetch_secret(&self.worker_ctx.client, &latest).await {
+            Ok(fetched) => fetched,
             Err(e) => {
-                error!("Failed to get enabled secrets: {}", e);
-                return;
+                error!("Failed to fetch latest version of secret: {}", e);
+                None
             }
-        };
+        }
+    }
 
-        for secret in &secrets {
-            self.sync_secret_to_cluster(secret, cluster).await;
+    /// Queue all enabled secrets for the clusters that became ready and were not
+    /// synced yet. The secrets are fetched once and shared by those clusters.
+    async fn handle_clusters_ready(&self, clusters: Vec<(Cluster, Span)>) {
+        let mut new_clusters = Vec::new();
+        for (cluster, span) in clusters {
+            let cluster_name = cluster.name_any();
+            if self.synced_clusters.read().await.contains(&cluster_name) {
+                debug!(
+                    parent: &span,
+                    "Cluster '{}' already synced, skipping secret sync on update",
+                    cluster_name
+                );
+                if let Some(worker) = self.workers.read().await.get(&cluster_name) {
+                    worker.update_cluster(cluster);
+                }
+            } else {
+                new_clusters.push((cluster, span));
+            }
+        }
+        if new_clusters.is_empty() {
+            return;
         }
 
+        let secrets = self.fetch_enabled_secrets().await;
+        for (cluster, span) in &new_clusters {
+            self.handle_cluster_ready(cluster, &secrets, span).await;
+        }
+    }
+
+    #[instrument(
+        parent = parent,
+        skip(self, cluster, secrets, parent),
+        fields(cluster = %cluster.name_any())
+    )]
+    async fn handle_cluster_ready(&self, cluster: &Cluster, secrets: &[Secret], parent: &Span) {
+        let cluster_name = cluster.name_any();
+        info!("New cluster became ready, queueing sync of all enabled secrets");
+
+        self.enqueue_secrets(cluster, secrets, Instant::now()).await;
+
         // Mark this cluster as synced
         self.synced_clusters.write().await.insert(cluster_name);
+        self.update_cluster_metrics().await;
     }
 
-    #[instrument(skip(self), fields(cluster = %name))]
-    async fn handle_cluster_not_ready(&self, name: &str) {
-        info!("Cluster '{}' is no longer ready, removing from synced set", name);
-        let mut synced = self.synced_clusters.write().await;
-        synced.remove(name);
+    #[instrument(
+        parent = parent,
+        skip(self, cluster, parent),
+        fields(cluster = %cluster.name_any())
+    )]
+    async fn handle_cluster_resync(&self, cluster: &Cluster, parent: &Span) {
+        let cluster_name = cluster.name_any();
+        info!("Resync requested, forgetting the synced state of the cluster");
+        self.synced_clusters.write().await.remove(&cluster_name);
+        self.worker_ctx.status.remove_cluster(&cluster_name);
+        if let Some(worker) = self.workers.read().await.get(&cluster_name) {
+            worker.update_cluster(cluster.clone());
+        }
+        self.handle_clusters_ready(vec![(cluster.clone(), Span::current())])
+            .await;
     }
 
-    /// Get all ready Rancher clusters (excluding the local cluster)
-    async fn get_ready_clusters(&self) -> crate::error::Result<Vec<Cluster>> {
-        let clusters: Api<Cluster> = Api::all(self.client.clone());
-        let cluster_list = clusters.list(&ListParams::default()).await?;
+    #[instrument(parent = parent, skip(self, parent), fields(cluster = %name))]
+    async fn handle_cluster_not_ready(&self, name: &str, parent: &Span) {
+        info!(
+            "Cluster '{}' is no longer ready, removing from synced set",
+            name
+        );
+        self.synced_clusters.write().await.remove(name);
+        self.update_cluster_metrics().await;
+
+        // Stop the worker; pending work is redone when the cluster becomes ready again
+        self.worker_ctx.status.remove_cluster(name);
+        self.worker_ctx.metrics.forget_cluster_backlog(Some(name));
+        self.worker_ctx
+            .status
+            .set_missing_permissions(name, Vec::new());
+        self.worker_ctx.metrics.forget_missing_permissions(name);
+        if let Some(worker) = self.workers.write().await.remove(name) {
+            let pending = worker.pending();
+            if pending > 0 {
+                info!(
+                    "Dropping {} pending secret(s) for cluster '{}'",
+                    pending, name
+                );
+            }
+        }
+    }
 
-        Ok(cluster_list
-            .items
-            .into_iter()
-            .filter(|c| c.is_ready() && !c.is_local())
-            .collect())
+    /// Fetch all enabled secrets, bounding each request by the sync timeout
+    async fn fetch_enabled_secrets(&self) -> Vec<Secret> {
+        let sync_timeout = self.config.borrow().sync_timeout;
+        fetch_enabled_secrets(&self.worker_ctx.client, &self.secrets, sync_timeout).await
     }
 
-    async fn sync_secret_to_clusters(&self, secret: &Secret, clusters: &[Cluster]) {
-        for cluster in clusters {
-            self.sync_secret_to_cluster(secret, cluster).await;
-        }
+    async fn update_cluster_metrics(&self) {
+        let ready = ready_clusters(&self.clusters).len();
+        let synced = self.synced_clusters.read().await.len();
+        self.worker_ctx.metrics.set_clusters(ready, synced);
     }
 
-    async fn sync_secret_to_cluster(&self, secret: &Secret, cluster: &Cluster) {
-        if let Err(e) = copy_secret_to_cluster(&self.client, secret, cluster, &self.config).await {
-            error!(
-                "Failed to sync secret {}/{} to cluster {}: {}",
-                secret.namespace().unwrap_or_default(),
-                secret.name_any(),
-                cluster.name_any(),
-                e
-            );
+    /// Queue secrets on the cluster's worker, starting the worker if needed.
+    /// `changed_at` is when the change that caused the sync was received.
+    async fn enqueue_secrets(&self, cluster: &Cluster, secrets: &[Secret], changed_at: Instant) {
+        let mut workers = self.workers.write().await;
+        let worker = workers.entry(cluster.name_any()).or_insert_with(|| {
+            debug!("Starting worker for cluster '{}'", cluster.name_any());
+            ClusterWorker::spawn(self.worker_ctx.clone(), cluster.clone())
+        });
+
+        worker.update_cluster(cluster.clone());
+        for secret in secrets {
+            if worker.enqueue(secret.clone(), changed_at) {
+                self.worker_ctx.metrics.record_coalesced_event();
+            }
         }
     }
 }
 
+/// Wait until both reconcilers have completed their initial listing
+async fn wait_for_caches(secrets: SecretStores, clusters: Store<Cluster>) -> anyhow::Result<()> {
+    clusters
+        .wait_until_ready()
+        .await
+        .map_err(|e| anyhow::anyhow!("Cluster cache unavailable: {}", e))?;
+    secrets
+        .wait_until_ready()
+        .await
+        .map_err(|e| anyhow::anyhow!("Secret cache unavailable: {}", e))?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::MockService;
+    use crate::config::StateConfigMapConfig;
+    use crate::constants::annotations;
+    use crate::reconcilers::ClusterReconciler;
+    use crate::sync::secrets::SecretMeta;
+    use crate::sync::status::SyncState;
+    use crate::test_utils::{MockService, make_cluster, make_secret, test_config};
+    use kube::core::PartialObjectMetaExt;
+    use kube::runtime::{reflector, watcher};
+    use std::time::SystemTime;
+    use tracing::Instrument;
 
     /// Check if a cluster has already been synced
     async fn is_cluster_synced(manager: &SyncManager, cluster_name: &str) -> bool {
@@ -234,7 +737,11 @@ mod tests {
 
     /// Mark a cluster as synced
     async fn mark_cluster_synced(manager: &SyncManager, cluster_name: &str) {
-        manager.synced_clusters.write().await.insert(cluster_name.to_string());
+        manager
+            .synced_clusters
+            .write()
+            .await
+            .insert(cluster_name.to_string());
     }
 
     /// Get the number of synced clusters
@@ -267,7 +774,9 @@ mod tests {
         assert!(is_cluster_synced(&manager, "test-cluster").await);
 
         // Handle not ready event
-        manager.handle_cluster_not_ready("test-cluster").await;
+        manager
+            .handle_cluster_not_ready("test-cluster", &Span::none())
+            .await;
 
         // Cluster should no longer be in synced set
         assert!(!is_cluster_synced(&manager, "test-cluster").await);
@@ -279,7 +788,9 @@ mod tests {
         let (manager, _handle) = create_test_manager();
 
         // Handle not ready for a cluster that was never synced - should not panic
-        manager.handle_cluster_not_ready("nonexistent-cluster").await;
+        manager
+            .handle_cluster_not_ready("nonexistent-cluster", &Span::none())
+            .await;
         assert_eq!(synced_cluster_count(&manager).await, 0);
     }
 
@@ -297,7 +808,9 @@ mod tests {
         assert!(is_cluster_synced(&manager, "cluster-c").await);
 
         // Remove one cluster
-        manager.handle_cluster_not_ready("cluster-b").await;
+        manager
+            .handle_cluster_not_ready("cluster-b", &Span::none())
+            .await;
 
         assert_eq!(synced_cluster_count(&manager).await, 2);
         assert!(is_cluster_synced(&manager, "cluster-a").await);
@@ -314,7 +827,9 @@ mod tests {
         assert!(is_cluster_synced(&manager, "test-cluster").await);
 
         // Cluster becomes not ready
-        manager.handle_cluster_not_ready("test-cluster").await;
+        manager
+            .handle_cluster_not_ready("test-cluster", &Span::none())
+            .await;
         assert!(!is_cluster_synced(&manager, "test-cluster").await);
 
         // Cluster becomes ready again - should not be in synced set
@@ -322,6 +837,464 @@ mod tests {
         assert!(!is_cluster_synced(&manager, "test-cluster").await);
     }
 
+    #[tokio::test]
+    async fn test_restore_state_records_synced_hashes() {
+        let state = serde_json::json!({
+            "apiVersion": "v1",
+            "kind": "ConfigMap",
+            "metadata": {"name": "outrider-sync-state-0", "namespace": "outrider-system"},
+            "data": {
+                "test-cluster": r#"{"secrets":{"default/creds":{"hash":"abc","syncedAt":"2023-11-14T22:13:20Z"}}}"#
+            }
+        });
+        let mock = MockService::new().on_get(
+            "/api/v1/namespaces/outrider-system/configmaps/outrider-sync-state-0",
+            200,
+            &state.to_string(),
+        );
+        let (mut manager, handle) = create_test_manager();
+        manager.state = Some(StateStore::new(
+            mock.into_client(),
+            &StateConfigMapConfig {
+                name: "outrider-sync-state".to_string(),
+                namespace: "outrider-system".to_string(),
+                shards: 1,
+            },
+        ));
+
+        manager.restore_state().await;
+
+        assert_eq!(
+            handle
+                .status()
+                .synced_hash("test-cluster", "default/creds")
+                .as_deref(),
+            Some("abc")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_state_save_is_counted() {
+        let (mut manager, handle) = create_test_manager();
+        // Writes fall through to the default 404 response
+        manager.state = Some(StateStore::new(
+            MockService::new().into_client(),
+            &StateConfigMapConfig {
+                name: "outrider-sync-state".to_string(),
+                namespace: "outrider-system".to_string(),
+                shards: 1,
+            },
+        ));
+        manager.worker_ctx.status.record(
+            "test-cluster",
+            "default/creds",
+            SyncState::Synced {
+                at: SystemTime::now(),
+                hash: "abc".to_string(),
+            },
+        );
+
+        manager.save_state().await;
+
+        assert!(
+            handle
+                .metrics()
+                .encode()
+                .contains("outrider_state_save_failures_total 1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cluster_resync_redoes_initial_sync() {
+        let (mut manager, _handle) = create_test_manager();
+        mark_cluster_synced(&manager, "test-cluster").await;
+        manager.worker_ctx.status.record(
+            "test-cluster",
+            "default/creds",
+            SyncState::Synced {
+                at: SystemTime::now(),
+                hash: "abc".to_string(),
+            },
+        );
+
+        manager
+            .debounce_event(
+                SyncEvent::ResyncCluster {
+                    cluster: make_cluster("test-cluster", true),
+                },
+                Span::none(),
+            )
+            .await;
+
+        // The synced state is forgotten, then rebuilt by the full sync
+        assert!(
+            manager
+                .worker_ctx
+                .status
+                .synced_hash("test-cluster", "default/creds")
+                .is_none()
+        );
+        assert!(is_cluster_synced(&manager, "test-cluster").await);
+        assert_eq!(manager.workers.read().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_ready_clusters_share_fetched_secrets() {
+        let (mut manager, _handle) = create_test_manager();
+        let (store, mut writer) = reflector::store();
+        let secret = make_enabled_secret("default", "creds");
+        writer.apply_watcher_event(&watcher::Event::Apply(make_meta(&secret)));
+        manager.secrets = store.into();
+        let mock = MockService::new().on_get(
+            "/api/v1/namespaces/default/secrets/creds",
+            200,
+            &serde_json::to_string(&secret).unwrap(),
+        );
+        manager.worker_ctx.client = mock.clone().into_client();
+        mark_cluster_synced(&manager, "cluster-c").await;
+
+        manager
+            .handle_clusters_ready(vec![
+                (make_cluster("cluster-a", true), Span::none()),
+                (make_cluster("cluster-b", true), Span::none()),
+                (make_cluster("cluster-c", true), Span::none()),
+            ])
+            .await;
+
+        let fetches = mock
+            .requests()
+            .iter()
+            .filter(|r| r.starts_with("GET /api/v1/namespaces/default/secrets/creds"))
+            .count();
+        assert_eq!(fetches, 1);
+        assert!(is_cluster_synced(&manager, "cluster-a").await);
+        assert!(is_cluster_synced(&manager, "cluster-b").await);
+        assert_eq!(manager.workers.read().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_secret_resync_supersedes_pending_change() {
+        let (mut manager, handle) = create_test_manager();
+        manager.worker_ctx.status.record(
+            "cluster-a",
+            "default/creds",
+            SyncState::Synced {
+                at: SystemTime::now(),
+                hash: "abc".to_string(),
+            },
+        );
+        manager
+            .debounce_event(
+                SyncEvent::SecretChanged {
+                    secret: make_secret("default", "creds", &[]),
+                },
+                Span::none(),
+            )
+            .await;
+
+        manager
+            .debounce_event(
+                SyncEvent::ResyncSecret {
+                    secret: make_secret("default", "creds", &[]),
+                },
+                Span::none(),
+            )
+            .await;
+
+        assert!(manager.next_flush().is_none());
+        assert!(
+            handle
+                .metrics()
+                .encode()
+                .contains("outrider_coalesced_events_total 1")
+        );
+        assert!(
+            manager
+                .worker_ctx
+                .status
+                .synced_hash("cluster-a", "default/creds")
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_starts_one_worker_per_cluster() {
+        let (manager, _handle) = create_test_manager();
+
+        manager
+            .enqueue_secrets(&make_cluster("cluster-a", true), &[], Instant::now())
+            .await;
+        manager
+            .enqueue_secrets(&make_cluster("cluster-a", true), &[], Instant::now())
+            .await;
+        manager
+            .enqueue_secrets(&make_cluster("cluster-b", true), &[], Instant::now())
+            .await;
+
+        assert_eq!(manager.workers.read().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_paused_cluster_holds_queued_secrets() {
+        let (manager, handle) = create_test_manager();
+        let mut cluster = make_cluster("test-cluster", true);
+        cluster
+            .annotations_mut()
+            .insert(annotations::PAUSED.to_string(), "true".to_string());
+
+        manager
+            .enqueue_secrets(
+                &cluster,
+                &[make_secret("default", "creds", &[])],
+                Instant::now(),
+            )
+            .await;
+        tokio::task::yield_now().await;
+
+        assert_eq!(manager.workers.read().await["test-cluster"].pending(), 1);
+        let metrics = handle.metrics().encode();
+        assert!(metrics.contains(r#"outrider_cluster_paused{cluster="test-cluster"} 1"#));
+        assert!(metrics.contains(r#"outrider_pending_secrets{cluster="test-cluster"} 1"#));
+    }
+
+    #[tokio::test]
+    async fn test_handle_cluster_not_ready_stops_worker() {
+        let (manager, _handle) = create_test_manager();
+
+        manager
+            .enqueue_secrets(&make_cluster("test-cluster", true), &[], Instant::now())
+            .await;
+        mark_cluster_synced(&manager, "test-cluster").await;
+
+        manager
+            .handle_cluster_not_ready("test-cluster", &Span::none())
+            .await;
+
+        assert!(manager.workers.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_deleted_cluster_stops_worker_and_drops_status() {
+        let (mut manager, handle) = create_test_manager();
+        let cluster = make_cluster("test-cluster", true);
+        manager.enqueue_secrets(&cluster, &[], Instant::now()).await;
+        mark_cluster_synced(&manager, "test-cluster").await;
+        handle.status().record(
+            "test-cluster",
+            "default/creds",
+            SyncState::Synced {
+                at: SystemTime::now(),
+                hash: "abc".to_string(),
+            },
+        );
+
+        let reconciler = ClusterReconciler::new(MockService::new().into_client(), handle.clone());
+        let (cache, _) = reflector::store();
+        reconciler
+            .handle_deleted(&watcher::Event::Delete(cluster), &cache)
+            .await;
+        let (event, span) = manager.event_rx.try_recv().unwrap();
+        manager.debounce_event(event, span).await;
+
+        assert!(manager.workers.read().await.is_empty());
+        assert!(!is_cluster_synced(&manager, "test-cluster").await);
+        assert!(
+            handle
+                .status()
+                .get("test-cluster", "default/creds")
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_step_down_stops_all_work() {
+        let (mut manager, _handle) = create_test_manager();
+
+        manager
+            .enqueue_secrets(&make_cluster("cluster-a", true), &[], Instant::now())
+            .await;
+        mark_cluster_synced(&manager, "cluster-a").await;
+        manager
+            .debounce_event(
+                SyncEvent::SecretChanged {
+                    secret: make_secret("default", "creds", &[]),
+                },
+                Span::none(),
+            )
+            .await;
+
+        manager.step_down().await;
+
+        assert!(manager.workers.read().await.is_empty());
+        assert!(manager.synced_clusters.read().await.is_empty());
+        assert!(manager.next_flush().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_events_carry_sending_span() {
+        let _subscriber = tracing::subscriber::set_default(tracing_subscriber::registry());
+        let (mut manager, handle) = create_test_manager();
+        let reconcile = tracing::info_span!("reconcile");
+
+        handle
+            .send(SyncEvent::SecretChanged {
+                secret: make_secret("default", "creds", &[]),
+            })
+            .instrument(reconcile.clone())
+            .await;
+
+        let (_, span) = manager.event_rx.recv().await.unwrap();
+        assert!(span.id().is_some());
+        assert_eq!(span.id(), reconcile.id());
+    }
+
+    #[tokio::test]
+    async fn test_reloaded_concurrency_resizes_permits() {
+        let (mut manager, _handle) = create_test_manager();
+        let permits = manager.worker_ctx.permits.clone();
+        assert_eq!(permits.available_permits(), 4);
+
+        manager.worker_ctx.set_max_concurrent_syncs(6);
+        assert_eq!(permits.available_permits(), 6);
+
+        let in_flight = permits.clone().acquire_owned().await.unwrap();
+        manager.worker_ctx.set_max_concurrent_syncs(2);
+        tokio::task::yield_now().await;
+        // The copy in flight keeps its permit
+        assert_eq!(permits.available_permits(), 1);
+
+        drop(in_flight);
+        tokio::task::yield_now().await;
+        assert_eq!(permits.available_permits(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_repeated_secret_events_are_coalesced() {
+        let (mut manager, handle) = create_test_manager();
+        let secret = make_secret("default", "creds", &[]);
+
+        for _ in 0..3 {
+            manager
+                .debounce_event(
+                    SyncEvent::SecretChanged {
+                        secret: secret.clone(),
+                    },
+                    Span::none(),
+                )
+                .await;
+        }
+        manager
+            .debounce_event(
+                SyncEvent::SecretChanged {
+                    secret: make_secret("default", "token", &[]),
+                },
+                Span::none(),
+            )
+            .await;
+
+        assert_eq!(manager.pending_secrets.len(), 2);
+        assert!(
+            handle
+                .metrics()
+                .encode()
+                .contains("outrider_coalesced_events_total 2")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cluster_not_ready_drops_pending_ready_event() {
+        let (mut manager, handle) = create_test_manager();
+
+        manager
+            .debounce_event(
+                SyncEvent::ClusterBecameReady {
+                    cluster: make_cluster("test-cluster", true),
+                },
+                Span::none(),
+            )
+            .await;
+        assert!(manager.next_flush().is_some());
+
+        manager
+            .debounce_event(
+                SyncEvent::ClusterBecameNotReady {
+                    name: "test-cluster".to_string(),
+                },
+                Span::none(),
+            )
+            .await;
+
+        assert!(manager.pending_clusters.is_empty());
+        assert!(manager.next_flush().is_none());
+        assert!(
+            handle
+                .metrics()
+                .encode()
+                .contains("outrider_coalesced_events_total 1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_latest_secret_uses_event_when_cache_matches() {
+        let (mut manager, _handle) = create_test_manager();
+        let (store, mut writer) = reflector::store();
+        let mut secret = make_enabled_secret("default", "creds");
+        secret.metadata.resource_version = Some("1".to_string());
+        writer.apply_watcher_event(&watcher::Event::Apply(make_meta(&secret)));
+        manager.secrets = store.into();
+
+        let latest = manager.latest_secret(&secret).await.unwrap();
+        assert_eq!(latest.metadata.resource_version.as_deref(), Some("1"));
+    }
+
+    #[tokio::test]
+    async fn test_latest_secret_fetches_newer_version() {
+        let (mut manager, _handle) = create_test_manager();
+        let (store, mut writer) = reflector::store();
+        let mut cached = make_enabled_secret("default", "creds");
+        cached.metadata.resource_version = Some("2".to_string());
+        writer.apply_watcher_event(&watcher::Event::Apply(make_meta(&cached)));
+        manager.secrets = store.into();
+        manager.worker_ctx.client = MockService::new()
+            .on_get(
+                "/api/v1/namespaces/default/secrets/creds",
+                200,
+                &serde_json::to_string(&cached).unwrap(),
+            )
+            .into_client();
+
+        let mut stale = make_enabled_secret("default", "creds");
+        stale.metadata.resource_version = Some("1".to_string());
+
+        let latest = manager.latest_secret(&stale).await.unwrap();
+        assert_eq!(latest.metadata.resource_version.as_deref(), Some("2"));
+    }
+
+    #[tokio::test]
+    async fn test_latest_secret_skips_deleted_or_disabled() {
+        let (mut manager, _handle) = create_test_manager();
+        let (store, mut writer) = reflector::store();
+        writer.apply_watcher_event(&watcher::Event::Apply(make_meta(&make_secret(
+            "default",
+            "disabled",
+            &[],
+        ))));
+        manager.secrets = store.into();
+
+        assert!(
+            manager
+                .latest_secret(&make_enabled_secret("default", "disabled"))
+                .await
+                .is_none()
+        );
+        assert!(
+            manager
+                .latest_secret(&make_enabled_secret("default", "deleted"))
+                .await
+                .is_none()
+        );
+    }
+
     #[tokio::test]
     async fn test_sync_manager_handle_clone() {
         let (_manager, handle) = create_test_manager();
@@ -330,26 +1303,31 @@ mod tests {
         let _handle2 = handle.clone();
     }
 
+    fn make_meta(secret: &Secret) -> SecretMeta {
+        secret.metadata.clone().into_response_partial()
+    }
+
+    fn make_enabled_secret(namespace: &str, name: &str) -> Secret {
+        let mut secret = make_secret(namespace, name, &[]);
+        secret
+            .annotations_mut()
+            .insert(annotations::ENABLED.to_string(), "true".to_string());
+        secret
+    }
+
     fn create_test_manager() -> (SyncManager, SyncManagerHandle) {
         let config = Config {
             default_target_namespace: "cattle-global-data".to_string(),
             testing_mode: true,
+            max_concurrent_syncs: 4,
+            debounce: Duration::from_secs(60),
+            ..test_config()
         };
-
-        let (event_tx, event_rx) = mpsc::channel(256);
+        let (secrets, _) = SecretStores::new(&config);
+        let (clusters, _) = reflector::store();
+        let (_, config) = watch::channel(config);
 
         // Use mock client that doesn't require real k8s connection
-        let client = MockService::new().into_client();
-
-        let manager = SyncManager {
-            client,
-            config,
-            event_rx,
-            initial_sync_done: Arc::new(AtomicBool::new(false)),
-            synced_clusters: Arc::new(RwLock::new(HashSet::new())),
-        };
-
-        let handle = SyncManagerHandle { event_tx };
-        (manager, handle)
+        SyncManager::new(MockService::new().into_client(), config, secrets, clusters)
     }
 }
@@ -3,8 +3,18 @@
 
 //! Secret and cluster synchronization logic.
 
+pub mod clusters;
+pub mod debounce;
 pub mod manager;
 pub mod secrets;
+pub mod state;
+pub mod status;
+pub mod worker;
 
+pub use clusters::{get_ready_clusters, ready_clusters};
 pub use manager::{SyncEvent, SyncManager, SyncManagerHandle};
-pub use secrets::{copy_secret_to_cluster, get_enabled_secrets};
+pub use secrets::{
+    CopyOutcome, SecretMeta, SecretStores, copy_secret_to_cluster, enabled_secrets,
+    fetch_enabled_secrets, get_enabled_secrets, source_secret_apis,
+};
+pub use status::{SyncState, SyncStatus};
@@ -4,38 +4,214 @@
 //! Secret listing, filtering, and copying utilities
 
 use crate::config::Config;
-use crate::constants::{annotations, OPERATOR_NAME};
+use crate::constants::sync::MAX_CONCURRENT_FETCHES;
+use crate::constants::{OPERATOR_NAME, annotations, labels};
 use crate::error::Result;
 use crate::kubernetes::{create_downstream_client, ensure_namespace_exists};
 use crate::types::cluster::Cluster;
+use futures::{StreamExt, stream};
 use k8s_openapi::api::core::v1::Secret;
 use kube::{
-    api::{ListParams, ObjectMeta, Patch, PatchParams},
     Api, Client, ResourceExt,
+    api::{ListParams, ObjectMeta, PartialObjectMeta, Patch, PatchParams},
+    runtime::{
+        reflector::{
+            self, ObjectRef, Store,
+            store::{Writer, WriterDropped},
+        },
+        watcher::Config as WatcherConfig,
+    },
 };
-use tracing::{info, instrument};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::timeout;
+use tracing::{debug, error, info, instrument};
+
+/// Metadata of a source secret, as held in the watch cache.
+/// Secret payloads are only fetched for enabled secrets, and never cached.
+pub type SecretMeta = PartialObjectMeta<Secret>;
+
+/// Read view over the secret metadata caches of all source namespaces
+#[derive(Clone)]
+pub struct SecretStores {
+    stores: Vec<Store<SecretMeta>>,
+}
+
+impl SecretStores {
+    /// Create an empty cache for each API returned by `source_secret_apis`,
+    /// returning the combined view and the writers in the same order
+    pub fn new(config: &Config) -> (Self, Vec<Writer<SecretMeta>>) {
+        let count = config.watch_namespaces.len().max(1);
+        let (stores, writers) = (0..count).map(|_| reflector::store()).unzip();
+        (Self { stores }, writers)
+    }
+
+    pub fn get(&self, obj_ref: &ObjectRef<SecretMeta>) -> Option<Arc<SecretMeta>> {
+        self.stores.iter().find_map(|store| store.get(obj_ref))
+    }
+
+    pub fn state(&self) -> Vec<Arc<SecretMeta>> {
+        self.stores.iter().flat_map(|store| store.state()).collect()
+    }
+
+    /// Wait until every cache has completed its initial listing
+    pub async fn wait_until_ready(&self) -> std::result::Result<(), WriterDropped> {
+        for store in &self.stores {
+            store.wait_until_ready().await?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Vec<Store<SecretMeta>>> for SecretStores {
+    fn from(stores: Vec<Store<SecretMeta>>) -> Self {
+        Self { stores }
+    }
+}
 
-/// Get all secrets that have the enabled annotation
-#[instrument(skip(client))]
-pub async fn get_enabled_secrets(client: &Client) -> Result<Vec<Secret>> {
-    let secrets: Api<Secret> = Api::all(client.clone());
-    let secret_list = secrets.list(&ListParams::default()).await?;
+impl From<Store<SecretMeta>> for SecretStores {
+    fn from(store: Store<SecretMeta>) -> Self {
+        Self {
+            stores: vec![store],
+        }
+    }
+}
 
-    Ok(secret_list
-        .items
+/// APIs to read source secrets from: one per configured watch namespace, or a
+/// single cluster-wide API when no namespaces are configured
+pub fn source_secret_apis(client: &Client, config: &Config) -> Vec<Api<Secret>> {
+    if config.watch_namespaces.is_empty() {
+        vec![Api::all(client.clone())]
+    } else {
+        config
+            .watch_namespaces
+            .iter()
+            .map(|namespace| Api::namespaced(client.clone(), namespace))
+            .collect()
+    }
+}
+
+/// Get all secrets that have the enabled label or annotation
+#[instrument(skip(client, config))]
+pub async fn get_enabled_secrets(client: &Client, config: &Config) -> Result<Vec<Secret>> {
+    let mut enabled = Vec::new();
+
+    for secrets in source_secret_apis(client, config) {
+        let secret_list = secrets.list(&secret_list_params(config)).await?;
+        enabled.extend(secret_list.items.into_iter().filter(is_secret_enabled));
+    }
+
+    Ok(enabled)
+}
+
+/// List parameters for source secrets, using the label selector when configured
+pub fn secret_list_params(config: &Config) -> ListParams {
+    if config.secret_label_selector {
+        ListParams::default().labels(labels::ENABLED_SELECTOR)
+    } else {
+        ListParams::default()
+    }
+}
+
+/// Watcher configuration for source secrets, using the label selector when configured
+pub fn secret_watcher_config(config: &Config) -> WatcherConfig {
+    if config.secret_label_selector {
+        WatcherConfig::default().labels(labels::ENABLED_SELECTOR)
+    } else {
+        WatcherConfig::default()
+    }
+}
+
+/// Get the metadata of all secrets in the store that have the enabled label or annotation
+pub fn enabled_secrets(store: &SecretStores) -> Vec<Arc<SecretMeta>> {
+    store
+        .state()
         .into_iter()
-        .filter(is_secret_enabled)
-        .collect())
+        .filter(|s| is_enabled(&s.metadata))
+        .collect()
+}
+
+/// Fetch the full contents of all enabled secrets in the store, a few at a time,
+/// giving each request `request_timeout` to complete.
+/// Secrets that cannot be fetched, e.g. because they were deleted since, are skipped.
+pub async fn fetch_enabled_secrets(
+    client: &Client,
+    store: &SecretStores,
+    request_timeout: Duration,
+) -> Vec<Secret> {
+    let fetches = enabled_secrets(store).into_iter().map(|meta| async move {
+        let fetched = timeout(request_timeout, fetch_secret(client, &meta)).await;
+        (meta, fetched)
+    });
+    let results: Vec<_> = stream::iter(fetches)
+        .buffered(MAX_CONCURRENT_FETCHES)
+        .collect()
+        .await;
+
+    let mut secrets = Vec::new();
+    for (meta, fetched) in results {
+        match fetched {
+            Ok(Ok(Some(secret))) => secrets.push(secret),
+            Ok(Ok(None)) => debug!(
+                "Secret {}/{} no longer exists, skipping",
+                meta.namespace().unwrap_or_default(),
+                meta.name_any()
+            ),
+            Ok(Err(e)) => error!(
+                "Failed to fetch secret {}/{}: {}",
+                meta.namespace().unwrap_or_default(),
+                meta.name_any(),
+                e
+            ),
+            Err(_) => error!(
+                "Timed out fetching secret {}/{} after {:?}",
+                meta.namespace().unwrap_or_default(),
+                meta.name_any(),
+                request_timeout
+            ),
+        }
+    }
+
+    secrets
 }
 
-/// Check if a secret has the enabled annotation set to "true"
+/// Fetch the full secret for cached metadata, returning None if it no longer exists
+pub async fn fetch_secret(client: &Client, meta: &SecretMeta) -> Result<Option<Secret>> {
+    let namespace = meta.namespace().unwrap_or_default();
+    let secrets: Api<Secret> = Api::namespaced(client.clone(), &namespace);
+    Ok(secrets.get_opt(&meta.name_any()).await?)
+}
+
+/// Check if a secret has the enabled label or annotation set to "true"
 pub fn is_secret_enabled(secret: &Secret) -> bool {
-    secret
-        .metadata
+    is_enabled(&secret.metadata)
+}
+
+/// Check if object metadata has the enabled label or annotation set to "true".
+/// The annotation is still accepted so existing secrets keep working while
+/// they are migrated to the label.
+pub fn is_enabled(metadata: &ObjectMeta) -> bool {
+    let label = metadata
+        .labels
+        .as_ref()
+        .and_then(|l| l.get(labels::ENABLED));
+    let annotation = metadata
         .annotations
         .as_ref()
-        .and_then(|a| a.get(annotations::ENABLED))
-        .is_some_and(|v| v == "true")
+        .and_then(|a| a.get(annotations::ENABLED));
+
+    label.or(annotation).is_some_and(|v| v == "true")
+}
+
+/// Key identifying a source secret as `namespace/name`
+pub fn secret_key(secret: &Secret) -> String {
+    format!(
+        "{}/{}",
+        secret.namespace().unwrap_or_default(),
+        secret.name_any()
+    )
 }
 
 /// Get the target namespace for a secret from its annotation or use the default
@@ -49,9 +225,35 @@ pub fn get_target_namespace<'a>(secret: &'a Secret, config: &'a Config) -> &'a s
         .unwrap_or(&config.default_target_namespace)
 }
 
-/// Copy a secret to a downstream cluster
+/// Result of copying a secret to a downstream cluster
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CopyOutcome {
+    /// The downstream secret was created or updated
+    Applied { hash: String },
+    /// The downstream secret already had the expected content, nothing was written
+    Unchanged { hash: String },
+    /// In a dry run, the downstream secret would have been created or updated.
+    /// Nothing was written, so its content hash is unknown.
+    DryRun,
+}
+
+impl CopyOutcome {
+    /// Content hash of the downstream secret, unless a dry run left it unchanged
+    pub fn hash(&self) -> Option<&str> {
+        match self {
+            CopyOutcome::Applied { hash } | CopyOutcome::Unchanged { hash } => Some(hash),
+            CopyOutcome::DryRun => None,
+        }
+    }
+}
+
+/// Copy a secret to a downstream cluster, through `downstream_client` when the
+/// caller already has one for the cluster.
+/// When `known_hash` matches the content hash of the expected downstream secret,
+/// the cluster is not contacted at all. Otherwise the hash recorded on the
+/// downstream secret is checked before writing.
 #[instrument(
-    skip(manager_client, secret, cluster, config),
+    skip(manager_client, downstream_client, secret, cluster, config, known_hash),
     fields(
         secret = %format!("{}/{}", secret.namespace().unwrap_or_default(), secret.name_any()),
         cluster = %cluster.name_any()
@@ -59,62 +261,212 @@ pub fn get_target_namespace<'a>(secret: &'a Secret, config: &'a Config) -> &'a s
 )]
 pub async fn copy_secret_to_cluster(
     manager_client: &Client,
+    downstream_client: Option<&Client>,
     secret: &Secret,
     cluster: &Cluster,
     config: &Config,
-) -> Result<()> {
+    known_hash: Option<&str>,
+) -> Result<CopyOutcome> {
     let secret_name = secret.name_any();
     let source_namespace = secret.namespace().unwrap_or_default();
     let target_namespace = get_target_namespace(secret, config);
+    let new_secret = create_downstream_secret(secret, target_namespace);
+    let hash = recorded_content_hash(&new_secret)
+        .unwrap_or_default()
+        .to_string();
+
+    if known_hash == Some(hash.as_str()) {
+        debug!(
+            "Secret {}/{} unchanged since last sync to cluster {}, skipping",
+            source_namespace,
+            secret_name,
+            cluster.name_any()
+        );
+        return Ok(CopyOutcome::Unchanged { hash });
+    }
 
-    info!(
+    debug!(
         "Copying secret {}/{} to cluster {}",
         source_namespace,
         secret_name,
         cluster.name_any()
     );
 
-    // Create client for downstream cluster
-    let downstream_client = create_downstream_client(manager_client, cluster, config).await?;
+    let downstream_client = match downstream_client {
+        Some(client) => client.clone(),
+        None => create_downstream_client(manager_client, cluster, config).await?,
+    };
+
+    let outcome = apply_downstream_secret(&downstream_client, &new_secret, config.dry_run).await?;
+
+    // Applied copies are logged by the worker
+    match &outcome {
+        CopyOutcome::Applied { .. } => debug!(
+            "Successfully copied secret {}/{} to cluster {}/{}",
+            source_namespace,
+            secret_name,
+            cluster.name_any(),
+            target_namespace
+        ),
+        CopyOutcome::Unchanged { .. } => debug!(
+            "Secret {}/{} is already up to date in cluster {}/{}",
+            source_namespace,
+            secret_name,
+            cluster.name_any(),
+            target_namespace
+        ),
+        // The changes it would make were logged by the apply
+        CopyOutcome::DryRun => {}
+    }
 
-    // Ensure target namespace exists in downstream cluster
-    ensure_namespace_exists(&downstream_client, target_namespace).await?;
+    Ok(outcome)
+}
 
-    // Copy the secret to downstream cluster
-    let downstream_secrets: Api<Secret> = Api::namespaced(downstream_client, target_namespace);
-    let new_secret = create_downstream_secret(secret, target_namespace);
+/// Apply a prepared downstream secret, unless the existing downstream secret
+/// already carries the same content hash.
+/// In a dry run the apply is only validated by the API server, and the
+/// changes it would make are logged.
+async fn apply_downstream_secret(
+    client: &Client,
+    new_secret: &Secret,
+    dry_run: bool,
+) -> Result<CopyOutcome> {
+    let secret_name = new_secret.name_any();
+    let target_namespace = new_secret.namespace().unwrap_or_default();
+    let hash = recorded_content_hash(new_secret)
+        .unwrap_or_default()
+        .to_string();
+    let downstream_secrets: Api<Secret> = Api::namespaced(client.clone(), &target_namespace);
+
+    let existing = downstream_secrets.get_opt(&secret_name).await?;
+    let namespace_created = match &existing {
+        Some(existing) if recorded_content_hash(existing) == Some(hash.as_str()) => {
+            return Ok(CopyOutcome::Unchanged { hash });
+        }
+        Some(_) => false,
+        // Ensure target namespace exists in downstream cluster
+        None => ensure_namespace_exists(client, &target_namespace, dry_run).await?,
+    };
+
+    let mut pp = PatchParams::apply(OPERATOR_NAME).force();
+    if dry_run {
+        info!(
+            "Dry run: would apply secret {}/{}: {}",
+            target_namespace,
+            secret_name,
+            describe_changes(existing.as_ref(), new_secret)
+        );
+        // The API server rejects even a dry run in a namespace that does not exist yet
+        if namespace_created {
+            return Ok(CopyOutcome::DryRun);
+        }
+        pp = pp.dry_run();
+    }
 
     // Apply the secret (create or update)
-    let pp = PatchParams::apply(OPERATOR_NAME).force();
     downstream_secrets
-        .patch(&secret_name, &pp, &Patch::Apply(&new_secret))
+        .patch(&secret_name, &pp, &Patch::Apply(new_secret))
         .await?;
 
-    info!(
-        "Successfully copied secret {}/{} to cluster {}/{}",
-        source_namespace,
-        secret_name,
-        cluster.name_any(),
-        target_namespace
-    );
+    if dry_run {
+        return Ok(CopyOutcome::DryRun);
+    }
+    Ok(CopyOutcome::Applied { hash })
+}
+
+/// Describe what applying `new` over `existing` changes, as `+key` (added),
+/// `~key` (changed) and `-key` (removed). Only key names are listed, never values.
+pub(crate) fn describe_changes(existing: Option<&Secret>, new: &Secret) -> String {
+    let Some(existing) = existing else {
+        let keys: Vec<&str> = new.data.iter().flatten().map(|(k, _)| k.as_str()).collect();
+        return format!("create with data keys [{}]", keys.join(", "));
+    };
+
+    // Labels and annotations set by others are left alone by server-side
+    // apply, so only data keys can be removed
+    let sections = [
+        (
+            "data",
+            map_changes(existing.data.as_ref(), new.data.as_ref(), true),
+        ),
+        (
+            "labels",
+            map_changes(
+                existing.metadata.labels.as_ref(),
+                new.metadata.labels.as_ref(),
+                false,
+            ),
+        ),
+        (
+            "annotations",
+            map_changes(
+                existing.metadata.annotations.as_ref(),
+                new.metadata.annotations.as_ref(),
+                false,
+            ),
+        ),
+    ];
+    let changes: Vec<String> = sections
+        .iter()
+        .filter(|(_, changes)| !changes.is_empty())
+        .map(|(section, changes)| format!("{} [{}]", section, changes.join(", ")))
+        .collect();
+
+    if changes.is_empty() {
+        "no changes".to_string()
+    } else {
+        changes.join(", ")
+    }
+}
 
-    Ok(())
+fn map_changes<V: PartialEq>(
+    old: Option<&BTreeMap<String, V>>,
+    new: Option<&BTreeMap<String, V>>,
+    removals: bool,
+) -> Vec<String> {
+    let empty = BTreeMap::new();
+    let old = old.unwrap_or(&empty);
+    let new = new.unwrap_or(&empty);
+
+    let mut changes = Vec::new();
+    for (key, value) in new {
+        match old.get(key) {
+            None => changes.push(format!("+{}", key)),
+            Some(old_value) if old_value != value => changes.push(format!("~{}", key)),
+            Some(_) => {}
+        }
+    }
+    if removals {
+        changes.extend(
+            old.keys()
+                .filter(|key| !new.contains_key(*key))
+                .map(|key| format!("-{}", key)),
+        );
+    }
+    changes
 }
 
-/// Create a downstream secret by cloning and filtering outrider annotations
-fn create_downstream_secret(secret: &Secret, target_namespace: &str) -> Secret {
+/// Create a downstream secret by cloning and filtering outrider labels and annotations.
+/// The content hash of the result is recorded as an annotation.
+pub(crate) fn create_downstream_secret(secret: &Secret, target_namespace: &str) -> Secret {
     let filtered_annotations = secret.metadata.annotations.as_ref().map(|a| {
         a.iter()
             .filter(|(k, _)| !k.starts_with("outrider.geeko.me/"))
             .map(|(k, v)| (k.clone(), v.clone()))
             .collect()
     });
+    let filtered_labels = secret.metadata.labels.as_ref().map(|l| {
+        l.iter()
+            .filter(|(k, _)| !k.starts_with("outrider.geeko.me/"))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    });
 
-    Secret {
+    let mut downstream = Secret {
         metadata: ObjectMeta {
             name: secret.metadata.name.clone(),
             namespace: Some(target_namespace.to_string()),
-            labels: secret.metadata.labels.clone(),
+            labels: filtered_labels,
             annotations: filtered_annotations,
             ..Default::default()
         },
@@ -122,48 +474,66 @@ fn create_downstream_secret(secret: &Secret, target_namespace: &str) -> Secret {
         string_data: secret.string_data.clone(),
         type_: secret.type_.clone(),
         immutable: secret.immutable,
-    }
+    };
+
+    let hash = content_hash(&downstream);
+    downstream
+        .annotations_mut()
+        .insert(annotations::CONTENT_HASH.to_string(), hash);
+    downstream
+}
+
+/// SHA-256 over the serialized secret, ignoring any recorded content hash
+pub fn content_hash(secret: &Secret) -> String {
+    let mut secret = secret.clone();
+    secret.annotations_mut().remove(annotations::CONTENT_HASH);
+
+    // Maps in the Secret type are ordered, so serialization is deterministic
+    let bytes = serde_json::to_vec(&secret).unwrap_or_default();
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// The content hash Outrider recorded on a secret, if any
+pub fn recorded_content_hash(secret: &Secret) -> Option<&str> {
+    secret
+        .metadata
+        .annotations
+        .as_ref()
+        .and_then(|a| a.get(annotations::CONTENT_HASH))
+        .map(|s| s.as_str())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{
+        MockService, make_secret, namespace_json, not_found_json, test_config,
+    };
     use k8s_openapi::ByteString;
+    use kube::core::PartialObjectMetaExt;
     use std::collections::BTreeMap;
 
-    fn make_secret(
-        name: &str,
-        namespace: &str,
+    const PASSWORD: &[(&str, &str)] = &[("password", "secret123")];
+
+    fn with_annotations(
+        mut secret: Secret,
         annotations: Option<BTreeMap<String, String>>,
     ) -> Secret {
-        Secret {
-            metadata: ObjectMeta {
-                name: Some(name.to_string()),
-                namespace: Some(namespace.to_string()),
-                annotations,
-                ..Default::default()
-            },
-            data: Some(BTreeMap::from([(
-                "password".to_string(),
-                ByteString("secret123".as_bytes().to_vec()),
-            )])),
-            type_: Some("Opaque".to_string()),
-            ..Default::default()
-        }
+        secret.metadata.annotations = annotations;
+        secret
     }
 
     fn make_config(default_namespace: &str) -> Config {
         Config {
             default_target_namespace: default_namespace.to_string(),
-            testing_mode: false,
+            ..test_config()
         }
     }
 
     #[test]
     fn test_is_secret_enabled_true() {
-        let secret = make_secret(
-            "my-secret",
-            "default",
+        let secret = with_annotations(
+            make_secret("default", "my-secret", PASSWORD),
             Some(BTreeMap::from([(
                 annotations::ENABLED.to_string(),
                 "true".to_string(),
@@ -175,9 +545,8 @@ mod tests {
 
     #[test]
     fn test_is_secret_enabled_false_value() {
-        let secret = make_secret(
-            "my-secret",
-            "default",
+        let secret = with_annotations(
+            make_secret("default", "my-secret", PASSWORD),
             Some(BTreeMap::from([(
                 annotations::ENABLED.to_string(),
                 "false".to_string(),
@@ -189,15 +558,14 @@ mod tests {
 
     #[test]
     fn test_is_secret_enabled_no_annotation() {
-        let secret = make_secret("my-secret", "default", None);
+        let secret = make_secret("default", "my-secret", PASSWORD);
         assert!(!is_secret_enabled(&secret));
     }
 
     #[test]
     fn test_is_secret_enabled_wrong_annotation() {
-        let secret = make_secret(
-            "my-secret",
-            "default",
+        let secret = with_annotations(
+            make_secret("default", "my-secret", PASSWORD),
             Some(BTreeMap::from([(
                 "some.other/annotation".to_string(),
                 "true".to_string(),
@@ -207,11 +575,165 @@ mod tests {
         assert!(!is_secret_enabled(&secret));
     }
 
+    fn make_meta(secret: Secret) -> SecretMeta {
+        secret.metadata.into_response_partial()
+    }
+
+    fn enabled_annotation() -> Option<BTreeMap<String, String>> {
+        Some(BTreeMap::from([(
+            annotations::ENABLED.to_string(),
+            "true".to_string(),
+        )]))
+    }
+
+    #[test]
+    fn test_enabled_secrets_from_store() {
+        let (store, mut writer) = kube::runtime::reflector::store();
+        let enabled = with_annotations(
+            make_secret("default", "enabled", PASSWORD),
+            enabled_annotation(),
+        );
+        writer.apply_watcher_event(&kube::runtime::watcher::Event::Apply(make_meta(enabled)));
+        writer.apply_watcher_event(&kube::runtime::watcher::Event::Apply(make_meta(
+            make_secret("default", "disabled", PASSWORD),
+        )));
+
+        let secrets = enabled_secrets(&store.into());
+
+        assert_eq!(secrets.len(), 1);
+        assert_eq!(secrets[0].name_any(), "enabled");
+    }
+
+    #[test]
+    fn test_secret_stores_span_all_namespaces() {
+        let mut config = make_config("default");
+        config.watch_namespaces = vec!["team-a".to_string(), "team-b".to_string()];
+
+        let (stores, mut writers) = SecretStores::new(&config);
+        assert_eq!(writers.len(), 2);
+
+        for (writer, namespace) in writers.iter_mut().zip(["team-a", "team-b"]) {
+            let secret = with_annotations(
+                make_secret(namespace, "creds", PASSWORD),
+                enabled_annotation(),
+            );
+            writer.apply_watcher_event(&kube::runtime::watcher::Event::Apply(make_meta(secret)));
+        }
+
+        assert_eq!(enabled_secrets(&stores).len(), 2);
+        assert!(
+            stores
+                .get(&ObjectRef::new("creds").within("team-b"))
+                .is_some()
+        );
+        assert!(
+            stores
+                .get(&ObjectRef::new("creds").within("default"))
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_enabled_secrets_skips_missing() {
+        let (store, mut writer) = kube::runtime::reflector::store();
+        let present = with_annotations(
+            make_secret("default", "present", PASSWORD),
+            enabled_annotation(),
+        );
+        let deleted = with_annotations(
+            make_secret("default", "deleted", PASSWORD),
+            enabled_annotation(),
+        );
+        writer.apply_watcher_event(&kube::runtime::watcher::Event::Apply(make_meta(
+            present.clone(),
+        )));
+        writer.apply_watcher_event(&kube::runtime::watcher::Event::Apply(make_meta(deleted)));
+
+        // The deleted secret falls through to the default 404 response
+        let mock = MockService::new().on_get(
+            "/api/v1/namespaces/default/secrets/present",
+            200,
+            &serde_json::to_string(&present).unwrap(),
+        );
+
+        let secrets =
+            fetch_enabled_secrets(&mock.into_client(), &store.into(), Duration::from_secs(1)).await;
+
+        assert_eq!(secrets.len(), 1);
+        assert_eq!(secrets[0].name_any(), "present");
+        assert_eq!(secrets[0].data, present.data);
+    }
+
+    #[test]
+    fn test_secret_key() {
+        let secret = make_secret("source-ns", "my-secret", PASSWORD);
+        assert_eq!(secret_key(&secret), "source-ns/my-secret");
+    }
+
+    #[test]
+    fn test_is_secret_enabled_label() {
+        let mut secret = make_secret("default", "my-secret", PASSWORD);
+        secret.metadata.labels = Some(BTreeMap::from([(
+            labels::ENABLED.to_string(),
+            "true".to_string(),
+        )]));
+
+        assert!(is_secret_enabled(&secret));
+    }
+
+    #[test]
+    fn test_is_secret_enabled_label_takes_precedence() {
+        let mut secret = with_annotations(
+            make_secret("default", "my-secret", PASSWORD),
+            Some(BTreeMap::from([(
+                annotations::ENABLED.to_string(),
+                "true".to_string(),
+            )])),
+        );
+        secret.metadata.labels = Some(BTreeMap::from([(
+            labels::ENABLED.to_string(),
+            "false".to_string(),
+        )]));
+
+        assert!(!is_secret_enabled(&secret));
+    }
+
+    #[test]
+    fn test_secret_selectors_follow_config() {
+        let mut config = make_config("default-ns");
+        assert!(secret_list_params(&config).label_selector.is_none());
+        assert!(secret_watcher_config(&config).label_selector.is_none());
+
+        config.secret_label_selector = true;
+        assert_eq!(
+            secret_list_params(&config).label_selector.as_deref(),
+            Some(labels::ENABLED_SELECTOR)
+        );
+        assert_eq!(
+            secret_watcher_config(&config).label_selector.as_deref(),
+            Some(labels::ENABLED_SELECTOR)
+        );
+    }
+
+    #[test]
+    fn test_create_downstream_secret_filters_outrider_labels() {
+        let mut secret = make_secret("source-ns", "my-secret", PASSWORD);
+        secret.metadata.labels = Some(BTreeMap::from([
+            (labels::ENABLED.to_string(), "true".to_string()),
+            ("app".to_string(), "web".to_string()),
+        ]));
+
+        let downstream = create_downstream_secret(&secret, "target-ns");
+
+        let labels = downstream.metadata.labels.unwrap();
+        assert!(!labels.contains_key(labels::ENABLED));
+        assert_eq!(labels.get("app").unwrap(), "web");
+    }
+
     #[test]
     fn test_get_target_namespace_from_annotation() {
-        let secret = make_secret(
-            "my-secret",
-            "default",
+        let secret = with_annotations(
+            make_secret("default", "my-secret", PASSWORD),
             Some(BTreeMap::from([(
                 annotations::NAMESPACE.to_string(),
                 "custom-namespace".to_string(),
@@ -224,7 +746,7 @@ mod tests {
 
     #[test]
     fn test_get_target_namespace_fallback_to_config() {
-        let secret = make_secret("my-secret", "default", None);
+        let secret = make_secret("default", "my-secret", PASSWORD);
         let config = make_config("default-ns");
 
         assert_eq!(get_target_namespace(&secret, &config), "default-ns");
@@ -232,9 +754,8 @@ mod tests {
 
     #[test]
     fn test_create_downstream_secret_filters_outrider_annotations() {
-        let secret = make_secret(
-            "my-secret",
-            "source-ns",
+        let secret = with_annotations(
+            make_secret("source-ns", "my-secret", PASSWORD),
             Some(BTreeMap::from([
                 (annotations::ENABLED.to_string(), "true".to_string()),
                 (annotations::NAMESPACE.to_string(), "target-ns".to_string()),
@@ -252,7 +773,7 @@ mod tests {
 
     #[test]
     fn test_create_downstream_secret_sets_target_namespace() {
-        let secret = make_secret("my-secret", "source-ns", None);
+        let secret = make_secret("source-ns", "my-secret", PASSWORD);
 
         let downstream = create_downstream_secret(&secret, "target-ns");
 
@@ -261,7 +782,7 @@ mod tests {
 
     #[test]
     fn test_create_downstream_secret_preserves_data() {
-        let secret = make_secret("my-secret", "source-ns", None);
+        let secret = make_secret("source-ns", "my-secret", PASSWORD);
 
         let downstream = create_downstream_secret(&secret, "target-ns");
 
@@ -269,9 +790,203 @@ mod tests {
         assert_eq!(downstream.type_, secret.type_);
     }
 
+    #[test]
+    fn test_create_downstream_secret_records_content_hash() {
+        let secret = make_secret("source-ns", "my-secret", PASSWORD);
+
+        let downstream = create_downstream_secret(&secret, "target-ns");
+
+        let hash = recorded_content_hash(&downstream).unwrap();
+        assert_eq!(hash, content_hash(&downstream));
+        assert_eq!(hash.len(), 64);
+    }
+
+    #[test]
+    fn test_content_hash_changes_with_data() {
+        let secret = make_secret("source-ns", "my-secret", PASSWORD);
+        let mut changed = secret.clone();
+        changed.data.as_mut().unwrap().insert(
+            "password".to_string(),
+            ByteString("other".as_bytes().to_vec()),
+        );
+
+        let a = create_downstream_secret(&secret, "target-ns");
+        let b = create_downstream_secret(&changed, "target-ns");
+
+        assert_ne!(recorded_content_hash(&a), recorded_content_hash(&b));
+    }
+
+    #[test]
+    fn test_content_hash_ignores_outrider_annotations() {
+        let plain = make_secret("source-ns", "my-secret", PASSWORD);
+        let annotated = with_annotations(
+            make_secret("source-ns", "my-secret", PASSWORD),
+            Some(BTreeMap::from([(
+                annotations::ENABLED.to_string(),
+                "true".to_string(),
+            )])),
+        );
+
+        let a = create_downstream_secret(&plain, "target-ns");
+        let b = create_downstream_secret(&annotated, "target-ns");
+
+        assert_eq!(recorded_content_hash(&a), recorded_content_hash(&b));
+    }
+
+    #[tokio::test]
+    async fn test_apply_skips_write_when_hash_matches() {
+        let secret = make_secret("source-ns", "my-secret", PASSWORD);
+        let downstream = create_downstream_secret(&secret, "target-ns");
+        let hash = recorded_content_hash(&downstream).unwrap().to_string();
+
+        // No PATCH response is registered, so a write would fail the test
+        let mock = MockService::new().on_get(
+            "/api/v1/namespaces/target-ns/secrets/my-secret",
+            200,
+            &serde_json::to_string(&downstream).unwrap(),
+        );
+
+        let outcome = apply_downstream_secret(&mock.into_client(), &downstream, false)
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, CopyOutcome::Unchanged { hash });
+    }
+
+    #[tokio::test]
+    async fn test_apply_writes_when_hash_differs() {
+        let secret = make_secret("source-ns", "my-secret", PASSWORD);
+        let downstream = create_downstream_secret(&secret, "target-ns");
+        let mut stale = downstream.clone();
+        stale
+            .annotations_mut()
+            .insert(annotations::CONTENT_HASH.to_string(), "stale".to_string());
+        let body = serde_json::to_string(&stale).unwrap();
+
+        let mock = MockService::new()
+            .on_get("/api/v1/namespaces/target-ns/secrets/my-secret", 200, &body)
+            .on_patch("/api/v1/namespaces/target-ns/secrets/my-secret", 200, &body);
+
+        let outcome = apply_downstream_secret(&mock.into_client(), &downstream, false)
+            .await
+            .unwrap();
+
+        assert!(matches!(outcome, CopyOutcome::Applied { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_apply_creates_namespace_for_new_secret() {
+        let secret = make_secret("source-ns", "my-secret", PASSWORD);
+        let downstream = create_downstream_secret(&secret, "target-ns");
+        let body = serde_json::to_string(&downstream).unwrap();
+
+        let mock = MockService::new()
+            .on_get(
+                "/api/v1/namespaces/target-ns/secrets/my-secret",
+                404,
+                &not_found_json("secrets", "my-secret"),
+            )
+            .on_get(
+                "/api/v1/namespaces/target-ns",
+                200,
+                &namespace_json("target-ns"),
+            )
+            .on_patch("/api/v1/namespaces/target-ns/secrets/my-secret", 200, &body);
+
+        let outcome = apply_downstream_secret(&mock.into_client(), &downstream, false)
+            .await
+            .unwrap();
+
+        assert!(matches!(outcome, CopyOutcome::Applied { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_apply_dry_run_validates_update() {
+        let secret = make_secret("source-ns", "my-secret", PASSWORD);
+        let downstream = create_downstream_secret(&secret, "target-ns");
+        let mut stale = downstream.clone();
+        stale
+            .annotations_mut()
+            .insert(annotations::CONTENT_HASH.to_string(), "stale".to_string());
+        let body = serde_json::to_string(&stale).unwrap();
+
+        let mock = MockService::new()
+            .on_get("/api/v1/namespaces/target-ns/secrets/my-secret", 200, &body)
+            .on_patch("/api/v1/namespaces/target-ns/secrets/my-secret", 200, &body);
+
+        let outcome = apply_downstream_secret(&mock.clone().into_client(), &downstream, true)
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, CopyOutcome::DryRun);
+        let patch = mock.requests().pop().unwrap();
+        assert!(patch.starts_with("PATCH /api/v1/namespaces/target-ns/secrets/my-secret?"));
+        assert!(patch.contains("dryRun=All"));
+    }
+
+    #[tokio::test]
+    async fn test_apply_dry_run_skips_secret_in_missing_namespace() {
+        let secret = make_secret("source-ns", "my-secret", PASSWORD);
+        let downstream = create_downstream_secret(&secret, "target-ns");
+
+        // No PATCH response is registered, so a write would fail the test
+        let mock = MockService::new()
+            .on_get(
+                "/api/v1/namespaces/target-ns/secrets/my-secret",
+                404,
+                &not_found_json("secrets", "my-secret"),
+            )
+            .on_get(
+                "/api/v1/namespaces/target-ns",
+                404,
+                &not_found_json("namespaces", "target-ns"),
+            )
+            .on_post("/api/v1/namespaces", 201, &namespace_json("target-ns"));
+
+        let outcome = apply_downstream_secret(&mock.clone().into_client(), &downstream, true)
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, CopyOutcome::DryRun);
+        assert!(!mock.requests().iter().any(|r| r.starts_with("PATCH")));
+    }
+
+    #[test]
+    fn test_describe_changes() {
+        let secret = make_secret("source-ns", "my-secret", PASSWORD);
+        let new = create_downstream_secret(&secret, "target-ns");
+        assert_eq!(
+            describe_changes(None, &new),
+            "create with data keys [password]"
+        );
+        assert_eq!(describe_changes(Some(&new), &new), "no changes");
+
+        let mut existing = new.clone();
+        existing.data = Some(BTreeMap::from([
+            ("password".to_string(), ByteString(b"old".to_vec())),
+            ("token".to_string(), ByteString(b"abc".to_vec())),
+        ]));
+        existing
+            .annotations_mut()
+            .insert(annotations::CONTENT_HASH.to_string(), "stale".to_string());
+        existing
+            .labels_mut()
+            .insert("team".to_string(), "platform".to_string());
+
+        let description = describe_changes(Some(&existing), &new);
+        assert_eq!(
+            description,
+            format!(
+                "data [~password, -token], annotations [~{}]",
+                annotations::CONTENT_HASH
+            )
+        );
+        assert!(!description.contains("secret123"));
+    }
+
     #[test]
     fn test_create_downstream_secret_preserves_name() {
-        let secret = make_secret("my-secret", "source-ns", None);
+        let secret = make_secret("source-ns", "my-secret", PASSWORD);
 
         let downstream = create_downstream_secret(&secret, "target-ns");
 
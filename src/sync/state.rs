@@ -0,0 +1,497 @@
+// Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sync state persisted in ConfigMaps, so a restart only copies secrets
+//! whose content changed instead of contacting every cluster again.
+
+use crate::config::StateConfigMapConfig;
+use crate::constants::{OPERATOR_NAME, annotations};
+use crate::error::Result;
+use crate::sync::secrets::SecretStores;
+use crate::sync::status::{SyncState, SyncStatus};
+use crate::types::cluster::Cluster;
+use futures::future::{join_all, try_join_all};
+use k8s_openapi::api::core::v1::ConfigMap;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+use k8s_openapi::chrono::{DateTime, Utc};
+use kube::{
+    Api, Client, ResourceExt,
+    api::{ObjectMeta, Patch, PatchParams},
+    runtime::reflector::Store,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::time::SystemTime;
+use tracing::warn;
+
+/// Secrets synced to a cluster, stored as JSON under the cluster's name in one
+/// of the state ConfigMaps
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClusterState {
+    /// Resync annotation of the cluster when the state was saved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resync_requested_at: Option<String>,
+    /// Synced secrets keyed by `namespace/name`
+    pub secrets: BTreeMap<String, SecretState>,
+}
+
+/// Last successful sync of a secret to a cluster
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretState {
+    /// Content hash of the downstream secret
+    pub hash: String,
+    pub synced_at: Time,
+    /// Resync annotation of the source secret when the state was saved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resync_requested_at: Option<String>,
+}
+
+/// Sync state of all clusters, keyed by cluster name
+pub type PersistedState = BTreeMap<String, ClusterState>;
+
+/// Current values of the resync annotation, keyed by cluster name and by
+/// secret `namespace/name`. Objects without the annotation are left out.
+#[derive(Debug, Default)]
+pub struct ResyncMarks {
+    pub clusters: HashMap<String, String>,
+    pub secrets: HashMap<String, String>,
+}
+
+impl ResyncMarks {
+    /// Read the resync annotations from the caches
+    pub fn from_caches(secrets: &SecretStores, clusters: &Store<Cluster>) -> Self {
+        let mark = |values: &BTreeMap<String, String>| {
+            values.get(annotations::RESYNC_REQUESTED_AT).cloned()
+        };
+        Self {
+            clusters: clusters
+                .state()
+                .iter()
+                .filter_map(|c| Some((c.name_any(), mark(c.annotations())?)))
+                .collect(),
+            secrets: secrets
+                .state()
+                .iter()
+                .filter_map(|s| {
+                    let key = format!("{}/{}", s.namespace()?, s.name_any());
+                    Some((key, mark(s.annotations())?))
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Take the successful syncs from `status`, recording the resync annotations
+/// they were made under
+pub fn capture(status: &SyncStatus, marks: &ResyncMarks) -> PersistedState {
+    let mut state = PersistedState::new();
+    for ((cluster, secret), sync_state) in status.snapshot() {
+        let SyncState::Synced { at, hash } = sync_state else {
+            continue;
+        };
+        let cluster_state = state
+            .entry(cluster.clone())
+            .or_insert_with(|| ClusterState {
+                resync_requested_at: marks.clusters.get(&cluster).cloned(),
+                ..Default::default()
+            });
+        cluster_state.secrets.insert(
+            secret.clone(),
+            SecretState {
+                hash,
+                synced_at: Time(DateTime::<Utc>::from(at)),
+                resync_requested_at: marks.secrets.get(&secret).cloned(),
+            },
+        );
+    }
+    state
+}
+
+/// Record the persisted syncs in `status`. A cluster or secret whose resync
+/// annotation changed since the state was saved was asked to be synced again,
+/// so its state is dropped. Returns the number of restored syncs.
+pub fn restore(state: PersistedState, status: &SyncStatus, marks: &ResyncMarks) -> usize {
+    let mut restored = 0;
+    for (cluster, cluster_state) in state {
+        if marks.clusters.get(&cluster) != cluster_state.resync_requested_at.as_ref() {
+            continue;
+        }
+        for (secret, secret_state) in cluster_state.secrets {
+            if marks.secrets.get(&secret) != secret_state.resync_requested_at.as_ref() {
+                continue;
+            }
+            status.record(
+                &cluster,
+                &secret,
+                SyncState::Synced {
+                    at: SystemTime::from(secret_state.synced_at.0),
+                    hash: secret_state.hash,
+                },
+            );
+            restored += 1;
+        }
+    }
+    restored
+}
+
+/// Reads and writes the persisted sync state, spread over a fixed set of
+/// ConfigMaps by cluster name
+pub struct StateStore {
+    config_maps: Api<ConfigMap>,
+    names: Vec<String>,
+    /// What was last loaded or saved per ConfigMap, to skip writes that change nothing
+    saved: Vec<Option<PersistedState>>,
+}
+
+impl StateStore {
+    pub fn new(client: Client, config: &StateConfigMapConfig) -> Self {
+        let names = config.config_map_names();
+        Self {
+            config_maps: Api::namespaced(client, &config.namespace),
+            saved: vec![None; names.len()],
+            names,
+        }
+    }
+
+    /// Load the persisted state from all ConfigMaps. A missing ConfigMap holds
+    /// no state.
+    pub async fn load(&mut self) -> Result<PersistedState> {
+        let config_maps =
+            try_join_all(self.names.iter().map(|name| self.config_maps.get_opt(name))).await?;
+
+        let mut state = PersistedState::new();
+        for (saved, config_map) in self.saved.iter_mut().zip(config_maps) {
+            let shard = config_map
+                .map(|config_map| from_config_map(&config_map))
+                .unwrap_or_default();
+            state.extend(shard.clone());
+            *saved = Some(shard);
+        }
+        Ok(state)
+    }
+
+    /// Save the state, writing only the ConfigMaps whose part of it changed since
+    /// it was last loaded or saved. All of them are attempted; the first error is
+    /// returned, and a failed ConfigMap is written again by the next save.
+    pub async fn save(&mut self, state: PersistedState) -> Result<()> {
+        let mut shards = vec![PersistedState::new(); self.names.len()];
+        for (cluster, cluster_state) in state {
+            shards[shard_of(&cluster, self.names.len())].insert(cluster, cluster_state);
+        }
+
+        let (config_maps, names, saved) = (&self.config_maps, &self.names, &self.saved);
+        let writes = shards
+            .into_iter()
+            .enumerate()
+            .filter(|(index, shard)| saved[*index].as_ref() != Some(shard))
+            .map(|(index, shard)| async move {
+                let name = &names[index];
+                let result = config_maps
+                    .patch(
+                        name,
+                        &PatchParams::apply(OPERATOR_NAME).force(),
+                        &Patch::Apply(&to_config_map(name, &shard)),
+                    )
+                    .await;
+                (index, shard, result)
+            });
+        let results = join_all(writes).await;
+
+        let mut first_error = None;
+        for (index, shard, result) in results {
+            match result {
+                Ok(_) => self.saved[index] = Some(shard),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        match first_error {
+            Some(e) => Err(e.into()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Index of the ConfigMap holding the state of a cluster, stable across restarts.
+/// A cluster found in another ConfigMap on load, e.g. after changing the number
+/// of ConfigMaps, is moved on the next save.
+fn shard_of(cluster: &str, shards: usize) -> usize {
+    let digest = Sha256::digest(cluster.as_bytes());
+    let mut prefix = [0; 8];
+    prefix.copy_from_slice(&digest[..8]);
+    (u64::from_be_bytes(prefix) % shards as u64) as usize
+}
+
+fn to_config_map(name: &str, state: &PersistedState) -> ConfigMap {
+    let data = state
+        .iter()
+        .map(|(cluster, cluster_state)| {
+            let json = serde_json::to_string(cluster_state).unwrap_or_default();
+            (cluster.clone(), json)
+        })
+        .collect();
+    ConfigMap {
+        metadata: ObjectMeta {
+            name: Some(name.to_string()),
+            ..Default::default()
+        },
+        data: Some(data),
+        ..Default::default()
+    }
+}
+
+/// Parse the state of each cluster. An entry that cannot be parsed is
+/// dropped, causing a full sync of that cluster.
+fn from_config_map(config_map: &ConfigMap) -> PersistedState {
+    config_map
+        .data
+        .iter()
+        .flatten()
+        .filter_map(|(cluster, json)| match serde_json::from_str(json) {
+            Ok(cluster_state) => Some((cluster.clone(), cluster_state)),
+            Err(e) => {
+                warn!(
+                    "Ignoring invalid sync state of cluster '{}': {}",
+                    cluster, e
+                );
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockService;
+    use std::time::Duration;
+
+    const CONFIG_MAPS: &str = "/api/v1/namespaces/outrider-system/configmaps";
+
+    fn synced(hash: &str) -> SyncState {
+        SyncState::Synced {
+            at: SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            hash: hash.to_string(),
+        }
+    }
+
+    fn store(mock: &MockService) -> StateStore {
+        StateStore::new(
+            mock.clone().into_client(),
+            &StateConfigMapConfig {
+                name: "outrider-sync-state".to_string(),
+                namespace: "outrider-system".to_string(),
+                shards: 2,
+            },
+        )
+    }
+
+    /// A cluster name whose state is kept in the given ConfigMap
+    fn cluster_in(shard: usize) -> String {
+        (0..)
+            .map(|i| format!("cluster-{}", i))
+            .find(|name| shard_of(name, 2) == shard)
+            .unwrap()
+    }
+
+    fn config_map_json(name: &str) -> String {
+        serde_json::json!({
+            "apiVersion": "v1",
+            "kind": "ConfigMap",
+            "metadata": {"name": name, "namespace": "outrider-system"}
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_capture_and_restore() {
+        let status = SyncStatus::new();
+        status.record("cluster-a", "default/creds", synced("abc"));
+        status.record("cluster-a", "default/token", synced("def"));
+        status.record(
+            "cluster-b",
+            "default/creds",
+            SyncState::Failed {
+                attempts: 1,
+                error: "forbidden".to_string(),
+            },
+        );
+        let marks = ResyncMarks {
+            secrets: HashMap::from([("default/token".to_string(), "1".to_string())]),
+            ..Default::default()
+        };
+
+        let state = capture(&status, &marks);
+        assert_eq!(state.len(), 1, "failed syncs are not persisted");
+        let cluster_a = &state["cluster-a"];
+        assert_eq!(
+            cluster_a.secrets["default/token"]
+                .resync_requested_at
+                .as_deref(),
+            Some("1")
+        );
+
+        let restored = SyncStatus::new();
+        assert_eq!(restore(state, &restored, &marks), 2);
+        assert_eq!(
+            restored.get("cluster-a", "default/creds"),
+            Some(synced("abc"))
+        );
+        assert_eq!(
+            restored.get("cluster-a", "default/token"),
+            Some(synced("def"))
+        );
+    }
+
+    #[test]
+    fn test_restore_drops_state_with_changed_resync_mark() {
+        let status = SyncStatus::new();
+        status.record("cluster-a", "default/creds", synced("abc"));
+        status.record("cluster-a", "default/token", synced("def"));
+        status.record("cluster-b", "default/creds", synced("abc"));
+        let state = capture(&status, &ResyncMarks::default());
+
+        // Resyncs requested while the operator was not running
+        let marks = ResyncMarks {
+            clusters: HashMap::from([("cluster-b".to_string(), "1".to_string())]),
+            secrets: HashMap::from([("default/token".to_string(), "1".to_string())]),
+        };
+        let restored = SyncStatus::new();
+
+        assert_eq!(restore(state, &restored, &marks), 1);
+        assert!(restored.get("cluster-a", "default/creds").is_some());
+        assert!(restored.get("cluster-a", "default/token").is_none());
+        assert!(restored.get("cluster-b", "default/creds").is_none());
+    }
+
+    #[test]
+    fn test_config_map_round_trip() {
+        let status = SyncStatus::new();
+        status.record("cluster-a", "default/creds", synced("abc"));
+        let state = capture(&status, &ResyncMarks::default());
+
+        let mut config_map = to_config_map("outrider-sync-state", &state);
+        assert_eq!(
+            config_map.data.as_ref().unwrap()["cluster-a"],
+            r#"{"secrets":{"default/creds":{"hash":"abc","syncedAt":"2023-11-14T22:13:20Z"}}}"#
+        );
+        config_map
+            .data
+            .as_mut()
+            .unwrap()
+            .insert("cluster-b".to_string(), "not json".to_string());
+
+        assert_eq!(from_config_map(&config_map), state);
+    }
+
+    #[test]
+    fn test_shard_of_is_stable() {
+        assert_eq!(shard_of("cluster-a", 1), 0);
+        assert_eq!(shard_of("cluster-a", 8), shard_of("cluster-a", 8));
+        assert!((0..100).all(|i| shard_of(&format!("cluster-{}", i), 8) < 8));
+        assert_ne!(cluster_in(0), cluster_in(1));
+    }
+
+    #[tokio::test]
+    async fn test_load_merges_config_maps() {
+        let state = PersistedState::from([(
+            "cluster-a".to_string(),
+            ClusterState {
+                secrets: BTreeMap::from([(
+                    "default/creds".to_string(),
+                    SecretState {
+                        hash: "abc".to_string(),
+                        synced_at: Time(DateTime::<Utc>::from(SystemTime::UNIX_EPOCH)),
+                        resync_requested_at: None,
+                    },
+                )]),
+                ..Default::default()
+            },
+        )]);
+        // The second ConfigMap does not exist yet
+        let mock = MockService::new().on_get(
+            &format!("{}/outrider-sync-state-0", CONFIG_MAPS),
+            200,
+            &serde_json::to_string(&to_config_map("outrider-sync-state-0", &state)).unwrap(),
+        );
+        let mut store = store(&mock);
+
+        assert_eq!(store.load().await.unwrap(), state);
+        let mut requests = mock.requests();
+        requests.sort();
+        assert_eq!(
+            requests,
+            vec![
+                format!("GET {}/outrider-sync-state-0", CONFIG_MAPS),
+                format!("GET {}/outrider-sync-state-1", CONFIG_MAPS),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_writes_changed_config_maps_only() {
+        let mock = MockService::new()
+            .on_patch(
+                &format!("{}/outrider-sync-state-0", CONFIG_MAPS),
+                200,
+                &config_map_json("outrider-sync-state-0"),
+            )
+            .on_patch(
+                &format!("{}/outrider-sync-state-1", CONFIG_MAPS),
+                200,
+                &config_map_json("outrider-sync-state-1"),
+            );
+        let mut store = store(&mock);
+        let status = SyncStatus::new();
+        status.record(&cluster_in(0), "default/creds", synced("abc"));
+        status.record(&cluster_in(1), "default/creds", synced("abc"));
+
+        store
+            .save(capture(&status, &ResyncMarks::default()))
+            .await
+            .unwrap();
+        assert_eq!(mock.requests().len(), 2);
+        assert!(mock.requests()[0].contains("fieldManager=outrider"));
+
+        // Unchanged state writes nothing, a change only its cluster's ConfigMap
+        store
+            .save(capture(&status, &ResyncMarks::default()))
+            .await
+            .unwrap();
+        status.record(&cluster_in(1), "default/creds", synced("def"));
+        store
+            .save(capture(&status, &ResyncMarks::default()))
+            .await
+            .unwrap();
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[2].starts_with(&format!("PATCH {}/outrider-sync-state-1?", CONFIG_MAPS)));
+    }
+
+    #[tokio::test]
+    async fn test_save_retries_failed_config_map() {
+        // Writes to the second ConfigMap fall through to the default 404 response
+        let mock = MockService::new().on_patch(
+            &format!("{}/outrider-sync-state-0", CONFIG_MAPS),
+            200,
+            &config_map_json("outrider-sync-state-0"),
+        );
+        let mut store = store(&mock);
+        let status = SyncStatus::new();
+        status.record(&cluster_in(0), "default/creds", synced("abc"));
+        status.record(&cluster_in(1), "default/creds", synced("abc"));
+        let state = capture(&status, &ResyncMarks::default());
+
+        assert!(store.save(state.clone()).await.is_err());
+        assert!(store.save(state).await.is_err());
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[2].starts_with(&format!("PATCH {}/outrider-sync-state-1?", CONFIG_MAPS)));
+    }
+}
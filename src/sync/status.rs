@@ -0,0 +1,287 @@
+// Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Last known sync outcome for each (cluster, secret) pair.
+
+use crate::constants::LOCAL_CLUSTER;
+use crate::sync::secrets::CopyOutcome;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// Outcome of the most recent attempt to sync a secret to a cluster
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncState {
+    /// The downstream secret matches the source, identified by its content hash
+    Synced { at: SystemTime, hash: String },
+    /// A dry run found the downstream secret would be created or updated.
+    /// Nothing was written, so no hash is known.
+    DryRun { at: SystemTime },
+    /// The last attempt failed with a retryable error and a retry is scheduled
+    Retrying {
+        attempts: u32,
+        error: String,
+        next_attempt: SystemTime,
+    },
+    /// The last attempt failed with a permanent error and will not be retried
+    /// until the secret or cluster changes
+    Failed { attempts: u32, error: String },
+}
+
+/// Shared, cheaply cloneable record of sync outcomes keyed by cluster and secret
+#[derive(Clone, Default)]
+pub struct SyncStatus {
+    states: Arc<Mutex<BTreeMap<(String, String), SyncState>>>,
+    /// Permissions found missing by the last access check of each cluster
+    missing_permissions: Arc<Mutex<BTreeMap<String, Vec<String>>>>,
+}
+
+impl SyncStatus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the outcome for a secret (`namespace/name`) on a cluster
+    pub fn record(&self, cluster: &str, secret: &str, state: SyncState) {
+        self.states
+            .lock()
+            .unwrap()
+            .insert((cluster.to_string(), secret.to_string()), state);
+    }
+
+    /// Record a successful copy, whether or not a write was actually made.
+    /// Writes and skips are counted by the metrics. A dry run that would have
+    /// written is recorded without a hash, so it never skips a later copy.
+    pub fn record_copy(&self, cluster: &str, secret: &str, outcome: &CopyOutcome) {
+        let at = SystemTime::now();
+        let state = match outcome.hash() {
+            Some(hash) => SyncState::Synced {
+                at,
+                hash: hash.to_string(),
+            },
+            None => SyncState::DryRun { at },
+        };
+        self.record(cluster, secret, state);
+    }
+
+    /// Content hash of the secret last synced to the cluster, if the last attempt succeeded
+    pub fn synced_hash(&self, cluster: &str, secret: &str) -> Option<String> {
+        match self.get(cluster, secret)? {
+            SyncState::Synced { hash, .. } => Some(hash),
+            _ => None,
+        }
+    }
+
+    /// Get the last outcome for a secret on a cluster
+    pub fn get(&self, cluster: &str, secret: &str) -> Option<SyncState> {
+        self.states
+            .lock()
+            .unwrap()
+            .get(&(cluster.to_string(), secret.to_string()))
+            .cloned()
+    }
+
+    /// Last outcome for every (cluster, secret) pair
+    pub fn snapshot(&self) -> BTreeMap<(String, String), SyncState> {
+        self.states.lock().unwrap().clone()
+    }
+
+    /// Last outcome for a secret on each cluster it was synced to
+    pub fn secret_states(&self, secret: &str) -> BTreeMap<String, SyncState> {
+        self.states
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|((_, s), _)| s == secret)
+            .map(|((cluster, _), state)| (cluster.clone(), state.clone()))
+            .collect()
+    }
+
+    /// Forget the outcome for a secret on a cluster
+    pub fn remove(&self, cluster: &str, secret: &str) {
+        self.states
+            .lock()
+            .unwrap()
+            .remove(&(cluster.to_string(), secret.to_string()));
+    }
+
+    /// Forget all outcomes for a secret, on every cluster
+    pub fn remove_secret(&self, secret: &str) {
+        self.states.lock().unwrap().retain(|(_, s), _| s != secret);
+    }
+
+    /// Forget all outcomes for a cluster
+    pub fn remove_cluster(&self, cluster: &str) {
+        self.states.lock().unwrap().retain(|(c, _), _| c != cluster);
+    }
+
+    /// Forget all outcomes and the access checks of downstream clusters. The
+    /// manager cluster is checked by every replica, so its findings are kept.
+    pub fn clear(&self) {
+        self.states.lock().unwrap().clear();
+        self.missing_permissions
+            .lock()
+            .unwrap()
+            .retain(|cluster, _| cluster == LOCAL_CLUSTER);
+    }
+
+    /// Record the permissions an access check found missing on a cluster.
+    /// An empty list forgets earlier findings.
+    pub fn set_missing_permissions(&self, cluster: &str, missing: Vec<String>) {
+        let mut all = self.missing_permissions.lock().unwrap();
+        if missing.is_empty() {
+            all.remove(cluster);
+        } else {
+            all.insert(cluster.to_string(), missing);
+        }
+    }
+
+    /// Permissions the last access check found missing on a cluster
+    pub fn missing_permissions(&self, cluster: &str) -> Vec<String> {
+        self.missing_permissions
+            .lock()
+            .unwrap()
+            .get(cluster)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Number of pairs currently waiting for a retry
+    pub fn retrying(&self) -> usize {
+        self.states
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|s| matches!(s, SyncState::Retrying { .. }))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_replaces_previous_state() {
+        let status = SyncStatus::new();
+        let now = SystemTime::now();
+
+        status.record(
+            "cluster-a",
+            "default/creds",
+            SyncState::Retrying {
+                attempts: 1,
+                error: "timeout".to_string(),
+                next_attempt: now,
+            },
+        );
+        assert_eq!(status.retrying(), 1);
+
+        let synced = SyncState::Synced {
+            at: now,
+            hash: "abc".to_string(),
+        };
+        status.record("cluster-a", "default/creds", synced.clone());
+        assert_eq!(status.retrying(), 0);
+        assert_eq!(status.get("cluster-a", "default/creds"), Some(synced));
+    }
+
+    #[test]
+    fn test_record_copy_records_hash() {
+        let status = SyncStatus::new();
+        let applied = CopyOutcome::Applied {
+            hash: "abc".to_string(),
+        };
+        let unchanged = CopyOutcome::Unchanged {
+            hash: "abc".to_string(),
+        };
+
+        status.record_copy("cluster-a", "default/creds", &applied);
+        status.record_copy("cluster-b", "default/creds", &unchanged);
+
+        assert_eq!(
+            status.synced_hash("cluster-b", "default/creds").as_deref(),
+            Some("abc")
+        );
+        assert_eq!(
+            status.synced_hash("cluster-a", "default/creds").as_deref(),
+            Some("abc")
+        );
+    }
+
+    #[test]
+    fn test_record_copy_keeps_no_hash_for_dry_run() {
+        let status = SyncStatus::new();
+
+        status.record_copy("cluster-a", "default/creds", &CopyOutcome::DryRun);
+
+        assert!(matches!(
+            status.get("cluster-a", "default/creds"),
+            Some(SyncState::DryRun { .. })
+        ));
+        assert_eq!(status.synced_hash("cluster-a", "default/creds"), None);
+    }
+
+    #[test]
+    fn test_synced_hash_none_after_failure() {
+        let status = SyncStatus::new();
+        status.record(
+            "cluster-a",
+            "default/creds",
+            SyncState::Failed {
+                attempts: 1,
+                error: "forbidden".to_string(),
+            },
+        );
+
+        assert!(status.synced_hash("cluster-a", "default/creds").is_none());
+    }
+
+    #[test]
+    fn test_remove_cluster() {
+        let status = SyncStatus::new();
+        let now = SystemTime::now();
+
+        let synced = SyncState::Synced {
+            at: now,
+            hash: "abc".to_string(),
+        };
+        status.record("cluster-a", "default/creds", synced.clone());
+        status.record("cluster-b", "default/creds", synced);
+
+        status.remove_cluster("cluster-a");
+
+        assert!(status.get("cluster-a", "default/creds").is_none());
+        assert!(status.get("cluster-b", "default/creds").is_some());
+    }
+
+    #[test]
+    fn test_missing_permissions() {
+        let status = SyncStatus::new();
+
+        status.set_missing_permissions("cluster-a", vec!["create namespaces".to_string()]);
+        assert_eq!(
+            status.missing_permissions("cluster-a"),
+            vec!["create namespaces".to_string()]
+        );
+        assert!(status.missing_permissions("cluster-b").is_empty());
+
+        status.set_missing_permissions("cluster-a", Vec::new());
+        assert!(status.missing_permissions("cluster-a").is_empty());
+    }
+
+    #[test]
+    fn test_clear_keeps_manager_cluster_permissions() {
+        let status = SyncStatus::new();
+        status.set_missing_permissions("cluster-a", vec!["create namespaces".to_string()]);
+        status.set_missing_permissions(LOCAL_CLUSTER, vec!["list secrets".to_string()]);
+
+        status.clear();
+
+        assert!(status.missing_permissions("cluster-a").is_empty());
+        assert_eq!(
+            status.missing_permissions(LOCAL_CLUSTER),
+            vec!["list secrets".to_string()]
+        );
+    }
+}
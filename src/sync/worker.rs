@@ -0,0 +1,894 @@
+// Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-cluster work queues, so a slow or unreachable cluster only delays its own work.
+
+use crate::config::Config;
+use crate::constants::access::RECHECK_INTERVAL_SECS;
+use crate::constants::retry::{RETRY_BASE_DELAY_SECS, RETRY_MAX_DELAY_SECS};
+use crate::error::OutriderError;
+use crate::kubernetes::access::{
+    downstream_permissions, kubeconfig_permission, missing_permissions,
+};
+use crate::kubernetes::{any_namespace_absent, create_downstream_client};
+use crate::metrics::Metrics;
+use crate::sync::secrets::{
+    CopyOutcome, SecretStores, copy_secret_to_cluster, get_target_namespace, is_enabled, secret_key,
+};
+use crate::sync::status::{SyncState, SyncStatus};
+use crate::types::cluster::Cluster;
+use k8s_openapi::api::core::v1::Secret;
+use kube::{Client, ResourceExt, runtime::reflector::ObjectRef};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tokio::sync::{Notify, Semaphore, watch};
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, sleep_until, timeout};
+use tracing::{Instrument, Span, debug, error, info, warn};
+
+/// Shared state needed by every cluster worker
+#[derive(Clone)]
+pub struct WorkerContext {
+    pub client: Client,
+    /// Current configuration, updated when the config file is reloaded
+    pub config: watch::Receiver<Config>,
+    /// Metadata of the source secrets, to drop retries of secrets removed since
+    pub secrets: SecretStores,
+    /// Bounds the number of copies in flight across all clusters
+    pub permits: Arc<Semaphore>,
+    /// Number of copies `permits` currently allows
+    max_concurrent_syncs: usize,
+    pub status: SyncStatus,
+    pub metrics: Metrics,
+}
+
+impl WorkerContext {
+    pub fn new(
+        client: Client,
+        config: watch::Receiver<Config>,
+        secrets: SecretStores,
+        status: SyncStatus,
+        metrics: Metrics,
+    ) -> Self {
+        let max_concurrent_syncs = config.borrow().max_concurrent_syncs;
+        Self {
+            client,
+            config,
+            secrets,
+            permits: Arc::new(Semaphore::new(max_concurrent_syncs)),
+            max_concurrent_syncs,
+            status,
+            metrics,
+        }
+    }
+
+    /// Snapshot of the current configuration
+    pub fn config(&self) -> Config {
+        self.config.borrow().clone()
+    }
+
+    /// Whether the source secret still exists and is enabled
+    fn is_source_enabled(&self, secret: &Secret) -> bool {
+        let Some(namespace) = secret.namespace() else {
+            return false;
+        };
+        let obj_ref = ObjectRef::new(&secret.name_any()).within(&namespace);
+        self.secrets
+            .get(&obj_ref)
+            .is_some_and(|meta| is_enabled(&meta.metadata))
+    }
+
+    /// Change the number of copies in flight across all clusters.
+    /// Lowering the limit takes effect as copies in flight finish.
+    pub fn set_max_concurrent_syncs(&mut self, limit: usize) {
+        match limit.cmp(&self.max_concurrent_syncs) {
+            Ordering::Greater => self.permits.add_permits(limit - self.max_concurrent_syncs),
+            Ordering::Less => {
+                let excess = (self.max_concurrent_syncs - limit) as u32;
+                let permits = self.permits.clone();
+                // Waits behind copies already queued for a permit, as the semaphore is fair
+                tokio::spawn(async move {
+                    if let Ok(taken) = permits.acquire_many_owned(excess).await {
+                        taken.forget();
+                    }
+                });
+            }
+            Ordering::Equal => {}
+        }
+        self.max_concurrent_syncs = limit;
+    }
+}
+
+/// A secret to apply to a cluster
+struct Job {
+    secret: Secret,
+    cluster: Cluster,
+    /// Number of previous failed attempts
+    attempts: u32,
+    /// When the source change that caused this job was seen
+    changed_at: Instant,
+    /// Span that queued the job, the parent of the copy's span
+    span: Span,
+}
+
+/// A failed copy waiting for its next attempt
+struct Retry {
+    secret: Secret,
+    attempts: u32,
+    changed_at: Instant,
+    span: Span,
+    due: Instant,
+}
+
+/// Pending work for a single cluster.
+/// Holds only the latest desired state of each secret, keyed by `namespace/name`,
+/// together with when that state was seen and the span that queued it.
+struct Backlog {
+    cluster: Cluster,
+    secrets: BTreeMap<String, (Secret, Instant, Span)>,
+    retries: BTreeMap<String, Retry>,
+    /// Key of the secret currently being applied
+    in_flight: Option<String>,
+    /// Stop once the queued secrets are applied, instead of waiting for more work
+    draining: bool,
+}
+
+impl Backlog {
+    fn new(cluster: Cluster) -> Self {
+        Self {
+            cluster,
+            secrets: BTreeMap::new(),
+            retries: BTreeMap::new(),
+            in_flight: None,
+            draining: false,
+        }
+    }
+
+    /// Queue a secret, replacing any older pending version.
+    /// A newer version also supersedes a scheduled retry of an older one.
+    /// Returns true if an older version was replaced.
+    fn push(&mut self, secret: Secret, changed_at: Instant, span: Span) -> bool {
+        let key = secret_key(&secret);
+        let retried = self.retries.remove(&key).is_some();
+        self.secrets
+            .insert(key, (secret, changed_at, span))
+            .is_some()
+            || retried
+    }
+
+    /// Schedule a retry of a failed job, unless a newer version of the secret is already queued
+    fn schedule_retry(&mut self, job: Job, due: Instant) {
+        let key = secret_key(&job.secret);
+        if !self.secrets.contains_key(&key) {
+            self.retries.insert(
+                key,
+                Retry {
+                    secret: job.secret,
+                    attempts: job.attempts + 1,
+                    changed_at: job.changed_at,
+                    span: job.span,
+                    due,
+                },
+            );
+        }
+    }
+
+    /// Take the next secret to apply, using the latest known cluster.
+    /// Queued secrets go first, followed by retries that are due.
+    fn pop(&mut self, now: Instant) -> Option<Job> {
+        if let Some((_, (secret, changed_at, span))) = self.secrets.pop_first() {
+            return Some(Job {
+                secret,
+                cluster: self.cluster.clone(),
+                attempts: 0,
+                changed_at,
+                span,
+            });
+        }
+
+        let key = self
+            .retries
+            .iter()
+            .find(|(_, r)| r.due <= now)
+            .map(|(k, _)| k.clone())?;
+        let retry = self.retries.remove(&key)?;
+        Some(Job {
+            secret: retry.secret,
+            cluster: self.cluster.clone(),
+            attempts: retry.attempts,
+            changed_at: retry.changed_at,
+            span: retry.span,
+        })
+    }
+
+    /// When the earliest scheduled retry is due
+    fn next_retry(&self) -> Option<Instant> {
+        self.retries.values().map(|r| r.due).min()
+    }
+
+    fn len(&self) -> usize {
+        self.secrets.len() + self.retries.len()
+    }
+
+    /// Keys of all secrets that are queued, waiting for a retry or being applied
+    fn unfinished(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self
+            .in_flight
+            .iter()
+            .chain(self.secrets.keys())
+            .chain(self.retries.keys())
+            .cloned()
+            .collect();
+        keys.sort();
+        keys.dedup();
+        keys
+    }
+}
+
+/// Outcome of the last permission check of a cluster
+#[derive(Default)]
+struct AccessCheck {
+    checked_at: Option<Instant>,
+    /// Target namespaces the check covered
+    namespaces: BTreeSet<String>,
+    /// Whether the check could not complete
+    failed: bool,
+    /// Descriptions of the missing permissions
+    missing: Vec<String>,
+}
+
+impl AccessCheck {
+    /// Whether to check the permissions before a copy to `namespace`: before the
+    /// first copy to each target namespace, and again once in a while as long as
+    /// permissions are missing or the check could not complete
+    fn due(&self, namespace: &str, now: Instant) -> bool {
+        match self.checked_at {
+            None => true,
+            Some(_) if !self.namespaces.contains(namespace) => true,
+            Some(at) => {
+                (self.failed || !self.missing.is_empty())
+                    && now >= at + Duration::from_secs(RECHECK_INTERVAL_SECS)
+            }
+        }
+    }
+}
+
+/// A queue and background worker that applies secrets to one downstream cluster.
+/// Secrets are applied one at a time, so writes to the same cluster stay ordered.
+/// The worker is stopped when this handle is dropped.
+pub struct ClusterWorker {
+    backlog: Arc<Mutex<Backlog>>,
+    notify: Arc<Notify>,
+    task: JoinHandle<()>,
+}
+
+impl ClusterWorker {
+    pub fn spawn(ctx: WorkerContext, cluster: Cluster) -> Self {
+        let backlog = Arc::new(Mutex::new(Backlog::new(cluster)));
+        let notify = Arc::new(Notify::new());
+        let task = tokio::spawn(run_worker(ctx, backlog.clone(), notify.clone()));
+
+        Self {
+            backlog,
+            notify,
+            task,
+        }
+    }
+
+    /// Queue a secret to be applied to this cluster, `changed_at` being when the
+    /// change was seen. The copy is traced as part of the current span.
+    /// Returns true if it replaced an older pending version of the same secret.
+    pub fn enqueue(&self, secret: Secret, changed_at: Instant) -> bool {
+        let replaced = self
+            .backlog
+            .lock()
+            .unwrap()
+            .push(secret, changed_at, Span::current());
+        if replaced {
+            debug!("Replaced pending secret with newer version");
+        }
+        self.notify.notify_one();
+        replaced
+    }
+
+    /// Update the cluster object used for subsequent copies.
+    /// Wakes the worker, as the cluster may no longer be paused.
+    pub fn update_cluster(&self, cluster: Cluster) {
+        self.backlog.lock().unwrap().cluster = cluster;
+        self.notify.notify_one();
+    }
+
+    /// Number of secrets waiting to be applied, including scheduled retries
+    pub fn pending(&self) -> usize {
+        self.backlog.lock().unwrap().len()
+    }
+
+    /// Let the worker apply the secrets that are queued, then stop.
+    /// Retries that are not yet due are left unfinished.
+    pub fn drain(&self) {
+        self.backlog.lock().unwrap().draining = true;
+        self.notify.notify_one();
+    }
+
+    /// Wait for a draining worker to stop
+    pub async fn stopped(&mut self) {
+        let _ = (&mut self.task).await;
+    }
+
+    /// Keys of the secrets that were not yet applied, including one in flight
+    pub fn unfinished(&self) -> Vec<String> {
+        self.backlog.lock().unwrap().unfinished()
+    }
+}
+
+impl Drop for ClusterWorker {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Whether writing to a cluster is paused, globally or through its annotation
+fn is_paused(config: &Config, cluster: &Cluster) -> bool {
+    config.paused || cluster.is_paused()
+}
+
+async fn run_worker(ctx: WorkerContext, backlog: Arc<Mutex<Backlog>>, notify: Arc<Notify>) {
+    // Wakes the worker when a reloaded configuration lifts the global pause
+    let mut config = ctx.config.clone();
+    let mut paused = false;
+    let mut access = AccessCheck::default();
+    loop {
+        loop {
+            let next = {
+                let mut queue = backlog.lock().unwrap();
+                let cluster_name = queue.cluster.name_any();
+                let now_paused = is_paused(&config.borrow(), &queue.cluster);
+                if now_paused != paused {
+                    paused = now_paused;
+                    if paused {
+                        info!(
+                            "Writing to cluster {} is paused, holding {} pending secret(s)",
+                            cluster_name,
+                            queue.len()
+                        );
+                    } else {
+                        info!(
+                            "Writing to cluster {} resumed, applying {} pending secret(s)",
+                            cluster_name,
+                            queue.len()
+                        );
+                    }
+                }
+                // Paused work stays queued, and newer versions keep replacing it
+                let job = if paused {
+                    None
+                } else {
+                    queue.pop(Instant::now())
+                };
+                queue.in_flight = job.as_ref().map(|job| secret_key(&job.secret));
+                ctx.metrics
+                    .set_cluster_backlog(&cluster_name, queue.len(), paused);
+                job
+            };
+            let Some(job) = next else {
+                break;
+            };
+            let retry = sync_secret(&ctx, &job, &mut access)
+                .instrument(job.span.clone())
+                .await;
+
+            let mut queue = backlog.lock().unwrap();
+            queue.in_flight = None;
+            if let Some(due) = retry {
+                queue.schedule_retry(job, due);
+            }
+        }
+
+        let (next_retry, draining) = {
+            let queue = backlog.lock().unwrap();
+            (queue.next_retry(), queue.draining)
+        };
+        // A paused worker stops right away, its queued secrets are reported as unfinished
+        if draining {
+            return;
+        }
+        let next_retry = async {
+            match next_retry {
+                Some(due) if !paused => sleep_until(due).await,
+                _ => std::future::pending().await,
+            }
+        };
+        tokio::select! {
+            _ = notify.notified() => {}
+            Ok(()) = config.changed() => {}
+            _ = next_retry => {}
+        }
+    }
+}
+
+/// Apply a secret to a cluster and record the outcome.
+/// Returns when to try again if the copy failed with a retryable error.
+async fn sync_secret(ctx: &WorkerContext, job: &Job, access: &mut AccessCheck) -> Option<Instant> {
+    let Ok(_permit) = ctx.permits.acquire().await else {
+        return None;
+    };
+
+    let Job {
+        secret,
+        cluster,
+        attempts,
+        changed_at,
+        ..
+    } = job;
+
+    let config = ctx.config();
+    let cluster_name = cluster.name_any();
+    let key = secret_key(secret);
+
+    // A retry applies the secret as it was at the first attempt, which must not
+    // bring back a secret that was deleted or disabled since
+    if *attempts > 0 && !ctx.is_source_enabled(secret) {
+        debug!(
+            "Secret {} was deleted or disabled, dropping its retry to cluster {}",
+            key, cluster_name
+        );
+        ctx.status.remove(&cluster_name, &key);
+        return None;
+    }
+    let known_hash = ctx.status.synced_hash(&cluster_name, &key);
+
+    let namespace = get_target_namespace(secret, &config);
+    let downstream = if access.due(namespace, Instant::now()) {
+        check_access(ctx, cluster, &config, namespace, access).await
+    } else {
+        None
+    };
+
+    let result = if !access.missing.is_empty() {
+        // Reported when checked, so the copy is not attempted
+        Err(OutriderError::MissingPermissions(access.missing.join(", ")))
+    } else {
+        match timeout(
+            config.sync_timeout,
+            copy_secret_to_cluster(
+                &ctx.client,
+                downstream.as_ref(),
+                secret,
+                cluster,
+                &config,
+                known_hash.as_deref(),
+            ),
+        )
+        .await
+        {
+            Ok(result) => result,
+            Err(_) => Err(OutriderError::SyncTimeout(format!(
+                "no response from cluster {} within {:?}",
+                cluster_name, config.sync_timeout
+            ))),
+        }
+    };
+
+    match result {
+        Ok(outcome) => {
+            match outcome {
+                CopyOutcome::Applied { .. } => {
+                    info!("Synced secret {} to cluster {}", key, cluster_name)
+                }
+                CopyOutcome::Unchanged { .. } => debug!(
+                    "Secret {} already up to date in cluster {}",
+                    key, cluster_name
+                ),
+                CopyOutcome::DryRun => info!(
+                    "Dry run: would sync secret {} to cluster {}",
+                    key, cluster_name
+                ),
+            }
+            ctx.status.record_copy(&cluster_name, &key, &outcome);
+            ctx.metrics
+                .record_sync(&cluster_name, &key, &outcome, changed_at.elapsed());
+            None
+        }
+        Err(e) if e.is_retryable() => {
+            ctx.metrics.record_failure(&cluster_name, &key, &e);
+            let attempts = attempts + 1;
+            let delay = retry_delay(attempts);
+            warn!(
+                "Failed to sync secret {} to cluster {} (attempt {}), retrying in {:?}: {}",
+                key, cluster_name, attempts, delay, e
+            );
+            ctx.status.record(
+                &cluster_name,
+                &key,
+                SyncState::Retrying {
+                    attempts,
+                    error: e.to_string(),
+                    next_attempt: SystemTime::now() + delay,
+                },
+            );
+            Some(Instant::now() + delay)
+        }
+        Err(e) => {
+            ctx.metrics.record_failure(&cluster_name, &key, &e);
+            let attempts = attempts + 1;
+            error!(
+                "Failed to sync secret {} to cluster {} with a permanent error, not retrying: {}",
+                key, cluster_name, e
+            );
+            ctx.status.record(
+                &cluster_name,
+                &key,
+                SyncState::Failed {
+                    attempts,
+                    error: e.to_string(),
+                },
+            );
+            None
+        }
+    }
+}
+
+/// Check that Outrider may read the kubeconfig of a cluster, and that the
+/// kubeconfig may apply secrets in `namespace` and the target namespaces
+/// checked before, and create those that do not exist yet, recording what is
+/// missing. Returns the downstream client built for the check, for the copy.
+/// A check that could not complete, e.g. because the cluster is unreachable,
+/// does not hold back the copies, which report the actual error, and is tried
+/// again after the recheck interval.
+async fn check_access(
+    ctx: &WorkerContext,
+    cluster: &Cluster,
+    config: &Config,
+    namespace: &str,
+    access: &mut AccessCheck,
+) -> Option<Client> {
+    let cluster_name = cluster.name_any();
+    let mut namespaces = access.namespaces.clone();
+    namespaces.insert(namespace.to_string());
+    let check = async {
+        let kubeconfig =
+            missing_permissions(&ctx.client, &[kubeconfig_permission(cluster)]).await?;
+        if !kubeconfig.is_empty() {
+            let missing = kubeconfig
+                .iter()
+                .map(|p| format!("{} in the manager cluster", p))
+                .collect();
+            return Ok((missing, None));
+        }
+        let downstream = create_downstream_client(&ctx.client, cluster, config).await?;
+        let create_namespaces = any_namespace_absent(&downstream, &namespaces).await?;
+        let required = downstream_permissions(&namespaces, create_namespaces);
+        let missing = missing_permissions(&downstream, &required).await?;
+        let missing = missing.iter().map(|p| p.to_string()).collect();
+        Ok::<(Vec<String>, _), OutriderError>((missing, Some(downstream)))
+    };
+
+    let result = timeout(config.sync_timeout, check).await;
+    access.checked_at = Some(Instant::now());
+    access.namespaces = namespaces;
+    access.failed = !matches!(result, Ok(Ok(_)));
+    let (missing, downstream) = match result {
+        Ok(Ok(checked)) => checked,
+        Ok(Err(e)) => {
+            debug!(
+                "Could not check permissions for cluster {}: {}",
+                cluster_name, e
+            );
+            return None;
+        }
+        Err(_) => {
+            debug!("Permission check of cluster {} timed out", cluster_name);
+            return None;
+        }
+    };
+
+    if !missing.is_empty() {
+        error!(
+            "Missing permissions for cluster {}, not copying secrets until they are granted: {}",
+            cluster_name,
+            missing.join(", ")
+        );
+    } else if !access.missing.is_empty() {
+        info!("Permissions for cluster {} were granted", cluster_name);
+    }
+    ctx.status
+        .set_missing_permissions(&cluster_name, missing.clone());
+    ctx.metrics
+        .set_missing_permissions(&cluster_name, missing.len());
+    access.missing = missing;
+    downstream
+}
+
+/// Exponential backoff for the given attempt, capped and with jitter.
+/// The returned delay lies between half and the full backoff.
+fn retry_delay(attempts: u32) -> Duration {
+    let exp = attempts.saturating_sub(1).min(16);
+    let backoff = RETRY_BASE_DELAY_SECS
+        .saturating_mul(1 << exp)
+        .min(RETRY_MAX_DELAY_SECS);
+    let backoff = Duration::from_secs(backoff);
+    backoff / 2 + backoff.mul_f64(fastrand::f64() / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{MockService, make_cluster, make_secret, test_config};
+
+    fn failed_job(secret: Secret, attempts: u32, changed_at: Instant) -> Job {
+        Job {
+            secret,
+            cluster: make_cluster("downstream", true),
+            attempts,
+            changed_at,
+            span: Span::none(),
+        }
+    }
+
+    #[test]
+    fn test_backlog_collapses_to_latest_version() {
+        let mut backlog = Backlog::new(make_cluster("downstream", true));
+        let now = Instant::now();
+        let later = now + Duration::from_secs(1);
+
+        assert!(!backlog.push(
+            make_secret("default", "creds", &[("value", "v1")]),
+            now,
+            Span::none()
+        ));
+        assert!(backlog.push(
+            make_secret("default", "creds", &[("value", "v2")]),
+            later,
+            Span::none()
+        ));
+        assert_eq!(backlog.secrets.len(), 1);
+
+        let job = backlog.pop(now).unwrap();
+        assert_eq!(job.secret.data.unwrap()["value"].0, b"v2");
+        assert_eq!(job.attempts, 0);
+        assert_eq!(job.changed_at, later);
+        assert!(backlog.pop(now).is_none());
+    }
+
+    #[test]
+    fn test_backlog_keeps_distinct_secrets() {
+        let mut backlog = Backlog::new(make_cluster("downstream", true));
+        let now = Instant::now();
+
+        backlog.push(
+            make_secret("default", "creds", &[("value", "v1")]),
+            now,
+            Span::none(),
+        );
+        backlog.push(
+            make_secret("other", "creds", &[("value", "v1")]),
+            now,
+            Span::none(),
+        );
+        backlog.push(
+            make_secret("default", "token", &[("value", "v1")]),
+            now,
+            Span::none(),
+        );
+
+        assert_eq!(backlog.secrets.len(), 3);
+    }
+
+    #[test]
+    fn test_backlog_pop_uses_latest_cluster() {
+        let mut backlog = Backlog::new(make_cluster("downstream", true));
+        backlog.push(
+            make_secret("default", "creds", &[("value", "v1")]),
+            Instant::now(),
+            Span::none(),
+        );
+
+        let mut updated = make_cluster("downstream", true);
+        updated.metadata.namespace = Some("fleet-default".to_string());
+        backlog.cluster = updated;
+
+        let job = backlog.pop(Instant::now()).unwrap();
+        assert_eq!(job.cluster.namespace().as_deref(), Some("fleet-default"));
+    }
+
+    #[test]
+    fn test_backlog_retry_waits_until_due() {
+        let mut backlog = Backlog::new(make_cluster("downstream", true));
+        let now = Instant::now();
+        let due = now + Duration::from_secs(10);
+
+        backlog.schedule_retry(
+            failed_job(make_secret("default", "creds", &[("value", "v1")]), 1, now),
+            due,
+        );
+
+        assert_eq!(backlog.len(), 1);
+        assert_eq!(backlog.next_retry(), Some(due));
+        assert!(backlog.pop(now).is_none());
+
+        // Retries keep the time of the original change, so latency includes backoff
+        let job = backlog.pop(due).unwrap();
+        assert_eq!(job.attempts, 2);
+        assert_eq!(job.changed_at, now);
+        assert_eq!(backlog.len(), 0);
+    }
+
+    #[test]
+    fn test_backlog_newer_version_supersedes_retry() {
+        let mut backlog = Backlog::new(make_cluster("downstream", true));
+        let now = Instant::now();
+
+        backlog.schedule_retry(
+            failed_job(make_secret("default", "creds", &[("value", "v1")]), 2, now),
+            now,
+        );
+        assert!(backlog.push(
+            make_secret("default", "creds", &[("value", "v2")]),
+            now,
+            Span::none()
+        ));
+
+        assert!(backlog.next_retry().is_none());
+        let job = backlog.pop(now).unwrap();
+        assert_eq!(job.secret.data.unwrap()["value"].0, b"v2");
+        assert_eq!(job.attempts, 0);
+    }
+
+    #[test]
+    fn test_backlog_retry_not_scheduled_over_newer_version() {
+        let mut backlog = Backlog::new(make_cluster("downstream", true));
+        let now = Instant::now();
+
+        backlog.push(
+            make_secret("default", "creds", &[("value", "v2")]),
+            now,
+            Span::none(),
+        );
+        backlog.schedule_retry(
+            failed_job(make_secret("default", "creds", &[("value", "v1")]), 0, now),
+            now,
+        );
+
+        assert_eq!(backlog.len(), 1);
+        assert!(backlog.next_retry().is_none());
+    }
+
+    #[test]
+    fn test_backlog_unfinished_includes_in_flight() {
+        let mut backlog = Backlog::new(make_cluster("downstream", true));
+        let now = Instant::now();
+
+        backlog.push(
+            make_secret("default", "token", &[("value", "v1")]),
+            now,
+            Span::none(),
+        );
+        backlog.schedule_retry(
+            failed_job(make_secret("other", "creds", &[("value", "v1")]), 0, now),
+            now,
+        );
+        backlog.in_flight = Some("default/creds".to_string());
+
+        assert_eq!(
+            backlog.unfinished(),
+            vec!["default/creds", "default/token", "other/creds"]
+        );
+    }
+
+    #[test]
+    fn test_retry_delay_grows_exponentially_with_jitter() {
+        for attempts in 1..=5 {
+            let full = Duration::from_secs(RETRY_BASE_DELAY_SECS << (attempts - 1));
+            let delay = retry_delay(attempts);
+            assert!(
+                delay >= full / 2 && delay <= full,
+                "attempt {}: {:?}",
+                attempts,
+                delay
+            );
+        }
+    }
+
+    #[test]
+    fn test_retry_delay_is_capped() {
+        let cap = Duration::from_secs(RETRY_MAX_DELAY_SECS);
+        assert!(retry_delay(100) <= cap);
+        assert!(retry_delay(u32::MAX) >= cap / 2);
+    }
+
+    #[test]
+    fn test_access_check_due() {
+        let now = Instant::now();
+        let recheck_at = now + Duration::from_secs(RECHECK_INTERVAL_SECS);
+        let mut access = AccessCheck::default();
+        assert!(access.due("default", now));
+
+        access.checked_at = Some(now);
+        access.namespaces.insert("default".to_string());
+        assert!(
+            !access.due("default", recheck_at),
+            "complete permissions are not checked again"
+        );
+        assert!(
+            access.due("team-a", now),
+            "a new target namespace is checked"
+        );
+
+        access.missing = vec!["create namespaces cluster-wide".to_string()];
+        assert!(!access.due("default", now));
+        assert!(access.due("default", recheck_at));
+
+        access.missing.clear();
+        access.failed = true;
+        assert!(
+            !access.due("default", now),
+            "a failed check is not retried per copy"
+        );
+        assert!(access.due("default", recheck_at));
+    }
+
+    #[tokio::test]
+    async fn test_failed_access_check_is_recorded() {
+        // The access reviews fall through to the default 404 response
+        let (_, config) = watch::channel(test_config());
+        let (secrets, _) = SecretStores::new(&test_config());
+        let ctx = WorkerContext::new(
+            MockService::new().into_client(),
+            config,
+            secrets,
+            SyncStatus::new(),
+            Metrics::new(),
+        );
+        let mut access = AccessCheck::default();
+
+        check_access(
+            &ctx,
+            &make_cluster("test-cluster", true),
+            &test_config(),
+            "default",
+            &mut access,
+        )
+        .await;
+
+        assert!(access.failed);
+        assert!(access.missing.is_empty(), "copies are not held back");
+        assert!(!access.due("default", Instant::now()));
+    }
+
+    #[tokio::test]
+    async fn test_retry_of_removed_secret_is_dropped() {
+        let mock = MockService::new();
+        let (_, config) = watch::channel(test_config());
+        // The source secret is no longer in the cache
+        let (secrets, _) = SecretStores::new(&test_config());
+        let status = SyncStatus::new();
+        let ctx = WorkerContext::new(
+            mock.clone().into_client(),
+            config,
+            secrets,
+            status.clone(),
+            Metrics::new(),
+        );
+        let job = failed_job(
+            make_secret("default", "creds", &[("value", "v1")]),
+            1,
+            Instant::now(),
+        );
+        status.record(
+            "downstream",
+            "default/creds",
+            SyncState::Failed {
+                attempts: 1,
+                error: "timeout".to_string(),
+            },
+        );
+
+        let retry = sync_secret(&ctx, &job, &mut AccessCheck::default()).await;
+
+        assert!(retry.is_none());
+        assert!(mock.requests().is_empty(), "the stale copy is not applied");
+        assert!(status.get("downstream", "default/creds").is_none());
+    }
+}
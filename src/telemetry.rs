@@ -0,0 +1,177 @@
+// Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Log output and trace export setup.
+
+use crate::config::{Config, LogFormat};
+use crate::constants::OPERATOR_NAME;
+use anyhow::{Context, Result};
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use tracing::{Level, Subscriber, info, warn};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::fmt::{self, MakeWriter};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+/// Keeps trace export running. Call [`Telemetry::shutdown`] before exiting,
+/// so spans that were not exported yet are flushed.
+pub struct Telemetry {
+    tracer_provider: Option<SdkTracerProvider>,
+}
+
+impl Telemetry {
+    pub async fn shutdown(self) {
+        let Some(provider) = self.tracer_provider else {
+            return;
+        };
+        // Blocks until the batch exporter has flushed
+        match tokio::task::spawn_blocking(move || provider.shutdown()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!("Failed to flush trace spans: {}", e),
+            Err(e) => warn!("Failed to flush trace spans: {}", e),
+        }
+    }
+}
+
+/// Install the global subscriber writing logs to stdout in the configured format,
+/// and exporting trace spans when an OTLP endpoint is configured
+pub fn init(config: &Config) -> Result<Telemetry> {
+    let log_filter = env_filter(config.log_level.as_deref())?;
+    let log_layer = match config.log_format {
+        LogFormat::Text => fmt::layer().with_filter(log_filter).boxed(),
+        LogFormat::Json => json_layer(std::io::stdout).with_filter(log_filter).boxed(),
+    };
+
+    let tracer_provider = config
+        .otlp_endpoint
+        .as_deref()
+        .map(tracer_provider)
+        .transpose()?;
+    // Independent of the log level, so traces are complete even when only warnings are logged
+    let trace_layer = tracer_provider.as_ref().map(|provider| {
+        tracing_opentelemetry::layer()
+            .with_tracer(provider.tracer(OPERATOR_NAME))
+            .with_filter(trace_filter())
+    });
+
+    tracing_subscriber::registry()
+        .with(log_layer)
+        .with(trace_layer)
+        .try_init()?;
+
+    if let Some(endpoint) = &config.otlp_endpoint {
+        info!("Exporting trace spans to {}", endpoint);
+    }
+    Ok(Telemetry { tracer_provider })
+}
+
+/// Install the subscriber for CLI commands, logging to stderr so that stdout
+/// only holds the command output
+pub fn init_cli(config: &Config) -> Result<()> {
+    let log_layer = fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_filter(env_filter(config.log_level.as_deref())?);
+    tracing_subscriber::registry().with(log_layer).try_init()?;
+    Ok(())
+}
+
+/// Batches spans and exports them over OTLP gRPC
+fn tracer_provider(endpoint: &str) -> Result<SdkTracerProvider> {
+    let exporter = SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()
+        .with_context(|| format!("Failed to create OTLP exporter for {}", endpoint))?;
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(OPERATOR_NAME).build())
+        .build())
+}
+
+/// Spans of the operator itself, and the kube-runtime reconcile span that
+/// starts the trace of a change. Excludes the HTTP and gRPC clients, which
+/// would otherwise also trace the export of spans.
+fn trace_filter() -> Targets {
+    Targets::new()
+        .with_target(env!("CARGO_CRATE_NAME"), Level::INFO)
+        .with_target("kube_runtime::controller", Level::INFO)
+}
+
+/// Filter from LOG_LEVEL, falling back to RUST_LOG
+fn env_filter(log_level: Option<&str>) -> Result<EnvFilter> {
+    match log_level {
+        Some(directives) => EnvFilter::try_new(directives)
+            .with_context(|| format!("Invalid value '{}' for LOG_LEVEL", directives)),
+        None => Ok(EnvFilter::from_default_env()),
+    }
+}
+
+/// One JSON object per event. Event fields are top-level keys; the fields of
+/// the current span (e.g. `cluster`, `secret`) are under `span`, and those of
+/// all enclosing spans under `spans`.
+fn json_layer<S, W>(writer: W) -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    fmt::layer()
+        .json()
+        .flatten_event(true)
+        .with_current_span(true)
+        .with_span_list(true)
+        .with_writer(writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_json_keeps_span_fields() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry().with(json_layer(move || writer.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let secret = tracing::info_span!("handle_secret_change", secret = "default/creds");
+            let _secret = secret.enter();
+            let copy = tracing::info_span!("copy_secret_to_cluster", cluster = "downstream");
+            let _copy = copy.enter();
+            tracing::info!(hash = "abc", "Copied secret");
+        });
+
+        let output = buffer.0.lock().unwrap();
+        let line: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(line["message"], "Copied secret");
+        assert_eq!(line["hash"], "abc");
+        assert_eq!(line["span"]["cluster"], "downstream");
+        assert_eq!(line["spans"][0]["secret"], "default/creds");
+    }
+
+    #[test]
+    fn test_invalid_log_level() {
+        assert!(env_filter(Some("outrider=loud")).is_err());
+        assert!(env_filter(Some("outrider=debug,kube=warn")).is_ok());
+    }
+}
@@ -3,45 +3,80 @@
 
 //! Test utilities for mocking Kubernetes API responses.
 
+use crate::config::{Config, LogFormat};
+use crate::types::cluster::{Cluster, ClusterSpec, ClusterStatus, Condition};
 use http::{Request, Response};
-use kube::client::Body;
+use k8s_openapi::ByteString;
+use k8s_openapi::api::core::v1::Secret;
 use kube::Client;
-use std::collections::HashMap;
+use kube::api::ObjectMeta;
+use kube::client::Body;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
+use std::time::Duration;
 use tower::Service;
 
+/// Mock responses keyed by (method, path), holding (status, body)
+type Responses = HashMap<(String, String), (u16, String)>;
+
 /// A mock HTTP service that returns predefined responses based on request paths.
 #[derive(Clone)]
 pub struct MockService {
-    responses: Arc<Mutex<HashMap<(String, String), (u16, String)>>>,
+    responses: Arc<Mutex<Responses>>,
+    requests: Arc<Mutex<Vec<String>>>,
+    /// Paths of GET requests that never get a response
+    hanging: Arc<Mutex<Vec<String>>>,
 }
 
 impl MockService {
     pub fn new() -> Self {
         Self {
             responses: Arc::new(Mutex::new(HashMap::new())),
+            requests: Arc::new(Mutex::new(Vec::new())),
+            hanging: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
     /// Add a response for GET requests matching the exact path
     pub fn on_get(self, path: &str, status: u16, body: &str) -> Self {
-        self.responses
-            .lock()
-            .unwrap()
-            .insert(("GET".to_string(), path.to_string()), (status, body.to_string()));
+        self.responses.lock().unwrap().insert(
+            ("GET".to_string(), path.to_string()),
+            (status, body.to_string()),
+        );
         self
     }
 
     /// Add a response for POST requests matching the exact path
     pub fn on_post(self, path: &str, status: u16, body: &str) -> Self {
-        self.responses
-            .lock()
-            .unwrap()
-            .insert(("POST".to_string(), path.to_string()), (status, body.to_string()));
+        self.responses.lock().unwrap().insert(
+            ("POST".to_string(), path.to_string()),
+            (status, body.to_string()),
+        );
+        self
+    }
+
+    /// Add a response for PATCH requests matching the exact path
+    pub fn on_patch(self, path: &str, status: u16, body: &str) -> Self {
+        self.responses.lock().unwrap().insert(
+            ("PATCH".to_string(), path.to_string()),
+            (status, body.to_string()),
+        );
+        self
+    }
+
+    /// Never respond to GET requests matching the exact path, like an
+    /// unreachable API server
+    pub fn hang_on_get(self, path: &str) -> Self {
+        self.hanging.lock().unwrap().push(path.to_string());
         self
     }
 
+    /// Requests received so far, as "METHOD path?query"
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+
     /// Build a kube Client from this mock service
     pub fn into_client(self) -> Client {
         Client::new(self, "https://kubernetes.default.svc")
@@ -86,10 +121,18 @@ impl Service<Request<Body>> for MockService {
     fn call(&mut self, req: Request<Body>) -> Self::Future {
         let method = req.method().to_string();
         let path = req.uri().path().to_string();
+        self.requests
+            .lock()
+            .unwrap()
+            .push(format!("{} {}", method, req.uri()));
 
         let response = self.find_response(&method, &path);
+        let hangs = method == "GET" && self.hanging.lock().unwrap().contains(&path);
 
         Box::pin(async move {
+            if hangs {
+                std::future::pending::<()>().await;
+            }
             match response {
                 Some((status, body)) => Ok(Response::builder()
                     .status(status)
@@ -110,6 +153,72 @@ impl Service<Request<Body>> for MockService {
     }
 }
 
+/// A configuration with short timeouts and no debouncing, for tests that build
+/// a manager or copy secrets
+pub fn test_config() -> Config {
+    Config {
+        default_target_namespace: "default".to_string(),
+        testing_mode: false,
+        max_concurrent_syncs: 1,
+        sync_timeout: Duration::from_secs(1),
+        debounce: Duration::ZERO,
+        secret_label_selector: false,
+        watch_namespaces: Vec::new(),
+        http_port: 8080,
+        leader_election: None,
+        shutdown_timeout: Duration::from_secs(1),
+        log_format: LogFormat::Text,
+        log_level: None,
+        otlp_endpoint: None,
+        dry_run: false,
+        admin_token: None,
+        paused: false,
+        state_config_map: None,
+    }
+}
+
+/// A Rancher cluster in `fleet-default` whose Ready condition follows `ready`
+pub fn make_cluster(name: &str, ready: bool) -> Cluster {
+    let mut cluster = Cluster::new(
+        name,
+        ClusterSpec {
+            kubernetes_version: None,
+            local: None,
+            display_name: None,
+        },
+    );
+    cluster.metadata.namespace = Some("fleet-default".to_string());
+    cluster.status = Some(ClusterStatus {
+        cluster_name: format!("c-{}", name),
+        client_secret_name: None,
+        ready: Some(ready),
+        conditions: Some(vec![Condition {
+            condition_type: "Ready".to_string(),
+            status: if ready { "True" } else { "False" }.to_string(),
+            message: None,
+        }]),
+    });
+    cluster
+}
+
+/// An Opaque secret holding `data` as key and value pairs
+pub fn make_secret(namespace: &str, name: &str, data: &[(&str, &str)]) -> Secret {
+    Secret {
+        metadata: ObjectMeta {
+            name: Some(name.to_string()),
+            namespace: Some(namespace.to_string()),
+            ..Default::default()
+        },
+        data: Some(
+            data.iter()
+                .map(|(key, value)| (key.to_string(), ByteString(value.as_bytes().to_vec())))
+                .collect::<BTreeMap<_, _>>(),
+        ),
+        type_: Some("Opaque".to_string()),
+        ..Default::default()
+    }
+}
+
 /// Create a mock namespace JSON response
 pub fn namespace_json(name: &str) -> String {
     serde_json::json!({
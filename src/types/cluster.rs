@@ -1,5 +1,6 @@
 // Copyright 2026, Jeroen van Erp <jeroen@geeko.me>
 // SPDX-License-Identifier: Apache-2.0
+use crate::constants::{LOCAL_CLUSTER, annotations};
 use kube::{CustomResource, ResourceExt};
 use serde::{Deserialize, Serialize};
 
@@ -32,7 +33,14 @@ impl Cluster {
 
     /// Check if this is the local/management cluster
     pub fn is_local(&self) -> bool {
-        self.name_any() == "local"
+        self.name_any() == LOCAL_CLUSTER
+    }
+
+    /// Check if writing secrets to this cluster is paused through its annotation
+    pub fn is_paused(&self) -> bool {
+        self.annotations()
+            .get(annotations::PAUSED)
+            .is_some_and(|v| v == "true")
     }
 
     /// Get the name of the kubeconfig secret for this cluster
@@ -43,6 +51,12 @@ impl Cluster {
             .unwrap_or_else(|| format!("{}-kubeconfig", self.name_any()))
     }
 
+    /// Get the namespace of the kubeconfig secret for this cluster
+    pub fn kubeconfig_secret_namespace(&self) -> String {
+        self.namespace()
+            .unwrap_or_else(|| "cattle-system".to_string())
+    }
+
     /// Get the internal cluster name from status
     pub fn internal_name(&self) -> String {
         self.status
@@ -77,22 +91,12 @@ pub struct Condition {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use kube::api::ObjectMeta;
-
-    fn make_cluster(name: &str, status: Option<ClusterStatus>) -> Cluster {
-        Cluster {
-            metadata: ObjectMeta {
-                name: Some(name.to_string()),
-                namespace: Some("fleet-default".to_string()),
-                ..Default::default()
-            },
-            spec: ClusterSpec {
-                kubernetes_version: None,
-                local: None,
-                display_name: None,
-            },
-            status,
-        }
+    use crate::test_utils::make_cluster;
+
+    fn with_status(name: &str, status: Option<ClusterStatus>) -> Cluster {
+        let mut cluster = make_cluster(name, false);
+        cluster.status = status;
+        cluster
     }
 
     fn make_ready_condition() -> Condition {
@@ -111,9 +115,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_paused() {
+        let mut cluster = with_status("test-cluster", None);
+        assert!(!cluster.is_paused());
+
+        cluster
+            .annotations_mut()
+            .insert(annotations::PAUSED.to_string(), "true".to_string());
+        assert!(cluster.is_paused());
+
+        cluster
+            .annotations_mut()
+            .insert(annotations::PAUSED.to_string(), "false".to_string());
+        assert!(!cluster.is_paused());
+    }
+
     #[test]
     fn test_is_ready_with_ready_condition() {
-        let cluster = make_cluster(
+        let cluster = with_status(
             "test-cluster",
             Some(ClusterStatus {
                 cluster_name: "c-12345".to_string(),
@@ -128,7 +148,7 @@ mod tests {
 
     #[test]
     fn test_is_ready_with_not_ready_condition() {
-        let cluster = make_cluster(
+        let cluster = with_status(
             "test-cluster",
             Some(ClusterStatus {
                 cluster_name: "c-12345".to_string(),
@@ -143,7 +163,7 @@ mod tests {
 
     #[test]
     fn test_is_ready_with_no_conditions() {
-        let cluster = make_cluster(
+        let cluster = with_status(
             "test-cluster",
             Some(ClusterStatus {
                 cluster_name: "c-12345".to_string(),
@@ -158,13 +178,13 @@ mod tests {
 
     #[test]
     fn test_is_ready_with_no_status() {
-        let cluster = make_cluster("test-cluster", None);
+        let cluster = with_status("test-cluster", None);
         assert!(!cluster.is_ready());
     }
 
     #[test]
     fn test_is_ready_with_multiple_conditions() {
-        let cluster = make_cluster(
+        let cluster = with_status(
             "test-cluster",
             Some(ClusterStatus {
                 cluster_name: "c-12345".to_string(),
@@ -186,19 +206,19 @@ mod tests {
 
     #[test]
     fn test_is_local_true() {
-        let cluster = make_cluster("local", None);
+        let cluster = with_status("local", None);
         assert!(cluster.is_local());
     }
 
     #[test]
     fn test_is_local_false() {
-        let cluster = make_cluster("downstream-cluster", None);
+        let cluster = with_status("downstream-cluster", None);
         assert!(!cluster.is_local());
     }
 
     #[test]
     fn test_kubeconfig_secret_name_from_status() {
-        let cluster = make_cluster(
+        let cluster = with_status(
             "test-cluster",
             Some(ClusterStatus {
                 cluster_name: "c-12345".to_string(),
@@ -213,7 +233,7 @@ mod tests {
 
     #[test]
     fn test_kubeconfig_secret_name_fallback() {
-        let cluster = make_cluster(
+        let cluster = with_status(
             "test-cluster",
             Some(ClusterStatus {
                 cluster_name: "c-12345".to_string(),
@@ -228,13 +248,13 @@ mod tests {
 
     #[test]
     fn test_kubeconfig_secret_name_no_status() {
-        let cluster = make_cluster("test-cluster", None);
+        let cluster = with_status("test-cluster", None);
         assert_eq!(cluster.kubeconfig_secret_name(), "test-cluster-kubeconfig");
     }
 
     #[test]
     fn test_internal_name_from_status() {
-        let cluster = make_cluster(
+        let cluster = with_status(
             "test-cluster",
             Some(ClusterStatus {
                 cluster_name: "c-12345".to_string(),
@@ -249,7 +269,7 @@ mod tests {
 
     #[test]
     fn test_internal_name_fallback() {
-        let cluster = make_cluster("test-cluster", None);
+        let cluster = with_status("test-cluster", None);
         assert_eq!(cluster.internal_name(), "test-cluster");
     }
 }